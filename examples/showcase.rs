@@ -70,10 +70,14 @@ fn setup(mut commands: Commands) {
         ..Default::default()
     });
 
-    commands.spawn().insert_bundle(PerspectiveCameraBundle {
-        transform: Transform::from_xyz(0.0, 0.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..Default::default()
-    });
+    commands
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        })
+        // Draws the gizmos
+        .insert(GizmoCamera::default());
     //.insert(FlyCam);
 }
 
@@ -84,6 +88,7 @@ fn persistent_gizmos(mut commands: Commands) {
             shape: GizmoShape::Empty { radius: 1.0 },
             wireframe: Color::rgba(1.0, 1.0, 0.0, 1.0),
             color: Color::rgba(0.6, 0.8, 0.2, 0.2),
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -97,6 +102,7 @@ fn persistent_gizmos(mut commands: Commands) {
             },
             wireframe: Color::WHITE, // Billboard doesn't have a gizmo
             color: Color::WHITE,
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -111,6 +117,7 @@ fn persistent_gizmos(mut commands: Commands) {
                 },
                 wireframe: Color::rgb_linear(1.0, 0.0, 0.0),
                 color: Color::rgba_linear(1.0, 0.2, 0.0, 0.2),
+                ..Default::default()
             },
             ..Default::default()
         })
@@ -135,6 +142,7 @@ fn persistent_gizmos(mut commands: Commands) {
             shape: GizmoShape::Sphere { radius: 0.5 },
             wireframe: Color::rgb_linear(0.0, 0.0, 1.0),
             color: Color::rgba_linear(0.1, 0.2, 0.9, 0.2),
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -145,6 +153,7 @@ fn persistent_gizmos(mut commands: Commands) {
             shape: GizmoShape::Hemisphere { radius: 0.5 },
             wireframe: Color::rgb_linear(1.0, 0.0, 1.0),
             color: Color::rgba_linear(0.6, 0.0, 0.6, 0.2),
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -158,6 +167,7 @@ fn persistent_gizmos(mut commands: Commands) {
             },
             wireframe: Color::WHITE,
             color: Color::WHITE,
+            ..Default::default()
         },
         ..Default::default()
     });
@@ -217,7 +227,7 @@ fn immediate_mode_gizmos_system(
             .into_iter()
             .map(|i| i as f32 / 33.0)
             .map(|x| Vec3::new(2.0 * x - 1.0, (PI * 1.5 * x + t).sin(), 0.0))
-            .collect::<SmallVec<_>>();
+            .collect::<SmallVec<[Vec3; 32]>>();
 
         // Start drawing
         context
@@ -0,0 +1,171 @@
+use bevy::{
+    asset::HandleId,
+    prelude::*,
+    render::mesh::VertexAttributeValues,
+    utils::HashMap,
+};
+
+use crate::{GizmoMaterial, Gizmos};
+
+/// Axis aligned bounding box
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    #[inline]
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Computes the bounds of the mesh `ATTRIBUTE_POSITION`, returns `None`
+    /// if the mesh has no positions
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        if let Some(VertexAttributeValues::Float3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            let mut iter = positions.iter().copied().map(Vec3::from);
+            let first = iter.next()?;
+            Some(iter.fold(Aabb::new(first, first), |aabb, p| Aabb {
+                min: aabb.min.min(p),
+                max: aabb.max.max(p),
+            }))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    #[inline]
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// One of the 8 corners, the bits 0, 1 and 2 of `i` pick the max of the x, y and z axes
+    #[inline]
+    pub fn corner(&self, i: usize) -> Vec3 {
+        Vec3::new(
+            if i & 1 == 0 { self.min.x } else { self.max.x },
+            if i & 2 == 0 { self.min.y } else { self.max.y },
+            if i & 4 == 0 { self.min.z } else { self.max.z },
+        )
+    }
+
+    /// The 12 edges of the box
+    pub fn edges(&self) -> [(Vec3, Vec3); 12] {
+        let edge = |a: usize, b: usize| (self.corner(a), self.corner(b));
+        [
+            edge(0, 1),
+            edge(2, 3),
+            edge(4, 5),
+            edge(6, 7),
+            edge(0, 2),
+            edge(1, 3),
+            edge(4, 6),
+            edge(5, 7),
+            edge(0, 4),
+            edge(1, 5),
+            edge(2, 6),
+            edge(3, 7),
+        ]
+    }
+
+    /// World space bounds of this box once transformed by `transform`,
+    /// (computed from the 8 transformed corners)
+    pub fn transformed(&self, transform: &GlobalTransform) -> Self {
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        for i in 0..8 {
+            let p = transform.mul_vec3(self.corner(i));
+            min = min.min(p);
+            max = max.max(p);
+        }
+        Aabb { min, max }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Settings for the [`AabbGizmosPlugin`]
+pub struct AabbGizmosConfig {
+    /// Draw mask used when drawing the boxes, see [`Gizmos::draw`]
+    pub mask: u32,
+    pub color: Color,
+}
+
+impl Default for AabbGizmosConfig {
+    fn default() -> Self {
+        Self {
+            mask: u32::MAX,
+            color: Color::rgb_linear(1.0, 1.0, 0.0),
+        }
+    }
+}
+
+/// Local space bounds of each mesh asset, so they are only computed once
+#[derive(Default)]
+struct MeshAabbCache {
+    bounds: HashMap<HandleId, Option<Aabb>>,
+}
+
+fn mesh_aabb_system(
+    config: Res<AabbGizmosConfig>,
+    gizmos: Res<Gizmos>,
+    meshes: Res<Assets<Mesh>>,
+    mut cache: ResMut<MeshAabbCache>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    // Skip the gizmos own meshes
+    query: Query<(&Handle<Mesh>, &GlobalTransform), Without<GizmoMaterial>>,
+) {
+    // Invalidate changed meshes
+    for event in mesh_events.iter() {
+        match event {
+            AssetEvent::Created { handle }
+            | AssetEvent::Modified { handle }
+            | AssetEvent::Removed { handle } => {
+                cache.bounds.remove(&handle.id);
+            }
+        }
+    }
+
+    // Immediate lines are written straight into the shared line meshes,
+    // so the boxes don't spawn an entity per mesh each frame
+    let cache = &mut *cache;
+    gizmos.draw(config.mask, |mut context| {
+        context.with_wireframe(config.color);
+
+        for (handle, global_transform) in query.iter() {
+            let aabb = cache
+                .bounds
+                .entry(handle.id)
+                .or_insert_with(|| meshes.get(handle).and_then(Aabb::from_mesh));
+
+            if let Some(aabb) = aabb {
+                let aabb = aabb.transformed(global_transform);
+                context.line_segments(&aabb.edges(), 0.0);
+            }
+        }
+    });
+}
+
+/// Opt-in plugin that draws the world space bounding box of every entity with a `Handle<Mesh>`
+#[derive(Default)]
+pub struct AabbGizmosPlugin;
+
+impl Plugin for AabbGizmosPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<AabbGizmosConfig>();
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.init_resource::<MeshAabbCache>()
+            .add_system(mesh_aabb_system.system());
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::{prelude::*, render::camera::Camera};
+
+use crate::{GizmoLifetime, GizmoStyle, GizmosContext};
+
+/// Projects world positions onto the screen of a camera, see [`GizmosContext::annotate`]
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoProjection {
+    pub view_proj: Mat4,
+    /// Screen size in physical pixels
+    pub screen_size: Vec2,
+}
+
+impl GizmoProjection {
+    /// Projection of the `camera`, `None` if the camera window doesn't exist
+    pub fn new(camera: &Camera, transform: &GlobalTransform, windows: &Windows) -> Option<Self> {
+        let window = windows.get(camera.window)?;
+        Some(Self {
+            view_proj: camera.projection_matrix * transform.compute_matrix().inverse(),
+            screen_size: Vec2::new(
+                window.physical_width() as f32,
+                window.physical_height() as f32,
+            ),
+        })
+    }
+
+    /// Position in pixels from the bottom left corner of the screen,
+    /// `None` when behind the camera
+    pub fn project(&self, position: Vec3) -> Option<Vec2> {
+        let clip = self.view_proj * position.extend(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+
+        let ndc = Vec2::new(clip.x, clip.y) / clip.w;
+        Some((ndc * 0.5 + Vec2::splat(0.5)) * self.screen_size)
+    }
+}
+
+/// Label box placed next to a projected world position, linked to it by a leader line;
+/// positions behind the camera or outside of the screen are pointed by an arrow at the screen edge
+///
+/// All sizes are in pixels
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoAnnotation {
+    /// From the projected position to the closest corner of the box,
+    /// the sign of each axis picks the side of the box
+    pub offset: Vec2,
+    pub size: Vec2,
+    /// Minimum distance kept from the screen edges
+    pub margin: f32,
+    pub arrow_size: f32,
+}
+
+impl Default for GizmoAnnotation {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::new(24.0, 24.0),
+            size: Vec2::new(96.0, 24.0),
+            margin: 8.0,
+            arrow_size: 12.0,
+        }
+    }
+}
+
+/// Where each part of a [`GizmoAnnotation`] goes on the screen, useful to place the label text
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoAnnotationLayout {
+    /// Projected position, `None` when behind the camera or outside of the screen
+    pub anchor: Option<Vec2>,
+    /// Bottom left corner of the box
+    pub min: Vec2,
+    /// Top right corner of the box
+    pub max: Vec2,
+    /// Tip and direction of the arrow pointing to an off screen position
+    pub arrow: Option<(Vec2, Vec2)>,
+}
+
+impl GizmoAnnotation {
+    pub fn layout(&self, projection: &GizmoProjection, position: Vec3) -> GizmoAnnotationLayout {
+        let screen = projection.screen_size;
+        let margin = Vec2::splat(self.margin);
+        // Keeps the box inside the screen
+        let clamp_box = |min: Vec2| {
+            min.max(margin)
+                .min((screen - margin - self.size).max(margin))
+        };
+
+        let anchor = projection
+            .project(position)
+            .filter(|p| p.cmpge(Vec2::ZERO).all() && p.cmple(screen).all());
+
+        if let Some(anchor) = anchor {
+            let corner = anchor + self.offset;
+            let min = clamp_box(Vec2::new(
+                if self.offset.x < 0.0 {
+                    corner.x - self.size.x
+                } else {
+                    corner.x
+                },
+                if self.offset.y < 0.0 {
+                    corner.y - self.size.y
+                } else {
+                    corner.y
+                },
+            ));
+
+            return GizmoAnnotationLayout {
+                anchor: Some(anchor),
+                min,
+                max: min + self.size,
+                arrow: None,
+            };
+        }
+
+        // Direction from the screen center, the sign of the clip position stays right even
+        // for positions behind the camera, unlike the perspective divide
+        let clip = projection.view_proj * position.extend(1.0);
+        let mut direction = Vec2::new(clip.x, clip.y) * screen;
+        direction = if direction.length_squared() > f32::EPSILON {
+            direction.normalize()
+        } else {
+            -Vec2::Y
+        };
+
+        // Tip at the screen edge, inset by the margin
+        let center = screen * 0.5;
+        let extents = (center - margin).max(Vec2::ZERO);
+        let t = (extents.x / direction.x.abs()).min(extents.y / direction.y.abs());
+        let tip = center + direction * t;
+
+        // Box right behind the arrow
+        let behind = tip - direction * (self.arrow_size + self.size.length() * 0.5);
+        let min = clamp_box(behind - self.size * 0.5);
+
+        GizmoAnnotationLayout {
+            anchor: None,
+            min,
+            max: min + self.size,
+            arrow: Some((tip, direction)),
+        }
+    }
+}
+
+impl<'a> GizmosContext<'a> {
+    /// Projects the `position` with the `projection` and draws the `annotation` box next to it,
+    /// the box is drawn in screen space and ignores the current transform matrix;
+    /// use [`GizmoAnnotation::layout`] to know where to place the label text
+    pub fn annotate(
+        &mut self,
+        projection: &GizmoProjection,
+        position: Vec3,
+        annotation: &GizmoAnnotation,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let lifetime = lifetime.into();
+        let position = self.matrix().transform_point3(position);
+        let layout = annotation.layout(projection, position);
+
+        let style = GizmoStyle {
+            screen: true,
+            ..*self.style()
+        };
+        self.with_style(style, |context| {
+            context.set_matrix(Transform::identity());
+
+            let (min, max) = (layout.min, layout.max);
+            context.line_loop(
+                [
+                    min.extend(0.0),
+                    Vec3::new(max.x, min.y, 0.0),
+                    max.extend(0.0),
+                    Vec3::new(min.x, max.y, 0.0),
+                ]
+                .iter()
+                .copied(),
+                lifetime,
+            );
+
+            if let Some(anchor) = layout.anchor {
+                // Leader line to the closest point of the box
+                let closest = anchor.max(min).min(max);
+                if closest.distance_squared(anchor) > f32::EPSILON {
+                    context.line_list(
+                        [anchor.extend(0.0), closest.extend(0.0)].iter().copied(),
+                        lifetime,
+                    );
+                }
+            }
+
+            if let Some((tip, direction)) = layout.arrow {
+                let back = -direction * annotation.arrow_size;
+                let side = Vec2::new(-back.y, back.x) * 0.5;
+                context.line_loop(
+                    [
+                        tip.extend(0.0),
+                        (tip + back + side).extend(0.0),
+                        (tip + back - side).extend(0.0),
+                    ]
+                    .iter()
+                    .copied(),
+                    lifetime,
+                );
+            }
+
+            context.pop_matrix();
+        })
+    }
+}
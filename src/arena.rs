@@ -0,0 +1,266 @@
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use crossbeam::queue::ArrayQueue;
+
+use crate::{
+    line::{LineEdit, LineKey, LineTopology},
+    mesh_helper::MeshEditXC,
+};
+
+/// How many arenas can be kept around without locking,
+/// should be more than enough to cover the amount of threads
+const ARENA_POOL_CAPACITY: usize = 64;
+
+/// Immediate mode lines written by a single [`crate::GizmosContext`], grouped by the mesh
+/// they will be written to; the buffers are cleared each frame but they keep their capacity around
+#[derive(Default)]
+pub(crate) struct LineArena {
+    batches: Vec<(LineKey, LineBuffers)>,
+}
+
+impl LineArena {
+    /// Buffers of the lines with the given `key`
+    pub fn batch(&mut self, key: LineKey) -> &mut LineBuffers {
+        let index = if let Some(index) = self.batches.iter().position(|(k, _)| *k == key) {
+            index
+        } else {
+            self.batches.push((key, LineBuffers::default()));
+            self.batches.len() - 1
+        };
+        &mut self.batches[index].1
+    }
+
+    /// Every batch with lines on it
+    pub fn batches(&mut self) -> impl Iterator<Item = (LineKey, &mut LineBuffers)> {
+        self.batches
+            .iter_mut()
+            .filter(|(_, buffers)| !buffers.is_empty())
+            .map(|(key, buffers)| (*key, buffers))
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.batches
+            .iter_mut()
+            .for_each(|(_, buffers)| buffers.clear());
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.batches.iter().all(|(_, buffers)| buffers.is_empty())
+    }
+}
+
+/// Lines that will end up in the same mesh
+#[derive(Default)]
+pub(crate) struct LineBuffers {
+    vertices: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    offsets: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl LineBuffers {
+    pub fn lines(
+        &mut self,
+        points: impl Iterator<Item = (Vec3, Color)>,
+        topology: LineTopology,
+        width: f32,
+    ) {
+        let v = self.vertices.len() as u32;
+        for (point, color) in points {
+            self.vertices.push(point.into());
+            self.colors.push(color.into());
+            self.offsets.push([0.0; 2]);
+        }
+
+        let count = self.vertices.len() as u32 - v;
+        self.edit().connect(v, count, topology, width);
+    }
+
+    /// Lines with all the points at `anchor`, then moved in screen space by their offsets (in pixels)
+    pub fn offset_lines(
+        &mut self,
+        anchor: Vec3,
+        offsets: impl Iterator<Item = ([f32; 2], Color)>,
+        topology: LineTopology,
+        width: f32,
+    ) {
+        let v = self.vertices.len() as u32;
+        for (offset, color) in offsets {
+            self.vertices.push(anchor.into());
+            self.colors.push(color.into());
+            self.offsets.push(offset);
+        }
+
+        let count = self.vertices.len() as u32 - v;
+        self.edit().connect(v, count, topology, width);
+    }
+
+    #[inline]
+    pub fn edit(&mut self) -> LineEdit {
+        LineEdit {
+            vertices: &mut self.vertices,
+            colors: &mut self.colors,
+            offsets: &mut self.offsets,
+            indices: &mut self.indices,
+        }
+    }
+
+    /// Moves the lines into the mesh
+    pub fn flush(&mut self, edit: &mut MeshEditXC) {
+        let v = edit.vertices.len() as u32;
+        edit.vertices.extend_from_slice(&self.vertices);
+        edit.colors.extend_from_slice(&self.colors);
+        edit.offsets.extend_from_slice(&self.offsets);
+        edit.indices.extend(self.indices.iter().map(|i| i + v));
+
+        self.clear();
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.colors.clear();
+        self.offsets.clear();
+        self.indices.clear();
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// Pool of [`LineArena`]s, each context takes one arena when it needs it and gives it
+/// back when it is dropped, so concurrent contexts never share the same arena
+pub(crate) struct LineArenas {
+    pool: ArrayQueue<LineArena>,
+    /// Only used when the pool is full
+    overflow: Mutex<Vec<LineArena>>,
+}
+
+impl Default for LineArenas {
+    fn default() -> Self {
+        Self {
+            pool: ArrayQueue::new(ARENA_POOL_CAPACITY),
+            overflow: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl LineArenas {
+    pub fn take(&self) -> LineArena {
+        self.pool
+            .pop()
+            .or_else(|| self.overflow.lock().unwrap().pop())
+            .unwrap_or_default()
+    }
+
+    pub fn give(&self, arena: LineArena) {
+        if let Err(arena) = self.pool.push(arena) {
+            self.overflow.lock().unwrap().push(arena);
+        }
+    }
+
+    /// Visits every arena with lines on it
+    pub fn for_each(&mut self, mut visitor: impl FnMut(&mut LineArena)) {
+        // Each arena is taken and given back once
+        for _ in 0..self.pool.len() {
+            if let Some(mut arena) = self.pool.pop() {
+                if !arena.is_empty() {
+                    (visitor)(&mut arena);
+                }
+                // Won't fail because the pool is exclusively borrowed
+                let _ = self.pool.push(arena);
+            }
+        }
+
+        for arena in self.overflow.get_mut().unwrap().iter_mut() {
+            if !arena.is_empty() {
+                (visitor)(arena);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::*;
+    use crate::Gizmos;
+
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = Cell::new(0);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(|count| count.get())
+    }
+
+    fn draw_frame(gizmos: &Gizmos) {
+        for i in 0..1000 {
+            gizmos.draw(!0, |mut context| {
+                let x = i as f32;
+                context.line_list(
+                    [
+                        Vec3::new(x, 0.0, 0.0),
+                        Vec3::new(x, 1.0, 0.0),
+                        Vec3::new(x, 1.0, 1.0),
+                        Vec3::new(x, 0.0, 1.0),
+                        Vec3::new(x, 0.0, 0.0),
+                    ],
+                    0.0,
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn immediate_lines_dont_allocate() {
+        let mut gizmos = Gizmos::default();
+
+        // Warm up, let the arenas grow
+        draw_frame(&gizmos);
+        gizmos.arenas.for_each(LineArena::clear);
+
+        let before = allocations();
+        draw_frame(&gizmos);
+        assert_eq!(allocations(), before);
+
+        let mut lines = 0;
+        gizmos.arenas.for_each(|arena| {
+            for (_, buffers) in arena.batches() {
+                lines += buffers.indices.len() / 2;
+            }
+            arena.clear();
+        });
+        assert_eq!(lines, 1000 * 4);
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{Camera, RenderLayers},
+        render_graph::base,
+    },
+};
+
+use crate::render_graph::GIZMO_PASS;
+
+/// Opts a camera into a gizmo pass, cameras without it don't draw any gizmo
+///
+/// Each gizmo camera also gets a private render layer, taken from the last layers, used to draw
+/// view dependent gizmos like the silhouettes only for it; avoid using those layers for anything else
+///
+/// **NOTE** Like any other pass, the gizmo pass finds its cameras by name so the camera must have one;
+/// also the camera stays in the pass even if this component is removed later
+#[derive(Debug, Clone, Copy)]
+pub struct GizmoCamera {
+    /// Gizmo pass that draws for this camera, see [`crate::add_gizmo_pass`]
+    pub pass: &'static str,
+    /// Size in pixels of the texture the camera renders into,
+    /// when `None` the physical size of the camera window is used
+    pub target_size: Option<Vec2>,
+}
+
+impl Default for GizmoCamera {
+    fn default() -> Self {
+        Self {
+            pass: GIZMO_PASS,
+            target_size: None,
+        }
+    }
+}
+
+/// Private render layer of a [`GizmoCamera`], see [`gizmo_camera_layers_system`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct GizmoCameraLayer(pub u8);
+
+/// Gives each new [`GizmoCamera`] a free private layer, starting from the last one;
+/// the first layer is never taken and cameras are left without one once every other layer is
+pub(crate) fn gizmo_camera_layers_system(
+    mut commands: Commands,
+    taken: Query<&GizmoCameraLayer>,
+    cameras: Query<(Entity, Option<&RenderLayers>), (With<GizmoCamera>, Without<GizmoCameraLayer>)>,
+) {
+    let mut free = (1..RenderLayers::TOTAL_LAYERS as u8)
+        .rev()
+        .filter(|layer| taken.iter().all(|taken| taken.0 != *layer));
+
+    for (entity, layers) in cameras.iter() {
+        let layer = if let Some(layer) = free.next() {
+            layer
+        } else {
+            return;
+        };
+
+        commands
+            .entity(entity)
+            .insert(GizmoCameraLayer(layer))
+            .insert(camera_layers(layers).with(layer));
+    }
+}
+
+/// Camera used to evaluate view dependent gizmos, prefers the 3d camera
+/// but takes anything if there's none; also returns the layers seen by the camera
+pub(crate) fn main_camera<'a>(
+    cameras: &'a Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+) -> Option<(&'a Camera, &'a GlobalTransform, RenderLayers)> {
+    cameras
+        .iter()
+        .find(|(camera, _, _)| camera.name.as_deref() == Some(base::camera::CAMERA_3D))
+        .or_else(|| cameras.iter().next())
+        .map(|(camera, transform, layers)| (camera, transform, camera_layers(layers)))
+}
+
+/// Layers seen by a camera, cameras without [`RenderLayers`] only see the first layer
+#[inline]
+pub(crate) fn camera_layers(layers: Option<&RenderLayers>) -> RenderLayers {
+    layers.copied().unwrap_or_default()
+}
+
+/// Returns true if the camera uses a orthographic projection
+#[inline]
+pub(crate) fn is_orthographic(camera: &Camera) -> bool {
+    camera.projection_matrix.w_axis.w == 1.0
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+use crate::Gizmos;
+
+/// Named set of gizmos, see [`GizmoCategories`]
+#[derive(Debug, Clone)]
+pub struct GizmoCategory {
+    pub name: String,
+    /// Single bit mask, used with [`Gizmos::draw`]
+    pub mask: u32,
+    pub enabled: bool,
+    /// Suggested wireframe color for this category, see [`Gizmos::draw_category`]
+    pub color: Color,
+}
+
+/// Hands out the [`Gizmos::mask`] bits by name, so crates don't need to agree on which bit
+/// belongs to who; toggling a category updates the [`Gizmos::mask`] right before the next update
+///
+/// ```ignore
+/// let physics = categories.register("physics", true, Color::GREEN);
+/// gizmos.draw(physics, |context| { ... });
+/// ```
+#[derive(Default)]
+pub struct GizmoCategories {
+    categories: Vec<GizmoCategory>,
+}
+
+impl GizmoCategories {
+    /// Registers a new category and returns his mask, registering the same name twice
+    /// will return the same mask and keep the previous settings
+    ///
+    /// **NOTE** Bits are given from the lowest to the highest, so avoid mixing categories with hand picked masks
+    ///
+    /// # Panics
+    ///
+    /// When more than 32 categories are registered
+    pub fn register(&mut self, name: &str, enabled: bool, color: Color) -> u32 {
+        if let Some(category) = self.get(name) {
+            return category.mask;
+        }
+
+        let bit = self.categories.len();
+        assert!(bit < 32, "too many gizmo categories, only 32 are supported");
+
+        let mask = 1 << bit;
+        self.categories.push(GizmoCategory {
+            name: name.to_string(),
+            mask,
+            enabled,
+            color,
+        });
+        mask
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&GizmoCategory> {
+        self.categories
+            .iter()
+            .find(|category| category.name == name)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut GizmoCategory> {
+        self.categories
+            .iter_mut()
+            .find(|category| category.name == name)
+    }
+
+    /// Mask of the category, `0` if it wasn't registered so nothing is drawn
+    #[inline]
+    pub fn mask(&self, name: &str) -> u32 {
+        self.get(name).map_or(0, |category| category.mask)
+    }
+
+    #[inline]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.get(name).map_or(false, |category| category.enabled)
+    }
+
+    #[inline]
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(category) = self.get_mut(name) {
+            category.enabled = enabled;
+        }
+    }
+
+    /// Flips the category enabled state, returns the new state
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if let Some(category) = self.get_mut(name) {
+            category.enabled = !category.enabled;
+            category.enabled
+        } else {
+            false
+        }
+    }
+
+    /// Categories in the registration order
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &GizmoCategory> {
+        self.categories.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut GizmoCategory> {
+        self.categories.iter_mut()
+    }
+}
+
+/// Copies the categories enabled state into the [`Gizmos::mask`], bits that
+/// don't belong to any category are left untouched
+pub(crate) fn gizmos_categories_system(
+    categories: Res<GizmoCategories>,
+    mut gizmos: ResMut<Gizmos>,
+) {
+    if !categories.is_changed() {
+        return;
+    }
+
+    for category in categories.iter() {
+        if category.enabled {
+            gizmos.mask |= category.mask;
+        } else {
+            gizmos.mask &= !category.mask;
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::{
+    arena::LineArena,
+    line::{Line, LineKey},
+    GizmoCommand,
+};
+
+/// Everything drawn into a channel by a single [`crate::Gizmos::channel`] call
+pub(crate) struct ChannelDraw {
+    pub name: String,
+    /// See [`crate::Gizmo::mask`]
+    pub mask: u32,
+    pub lines: LineArena,
+    /// Only [`GizmoCommand::Shape`]s
+    pub shapes: Vec<GizmoCommand>,
+}
+
+impl ChannelDraw {
+    pub fn new(name: &str, mask: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            mask,
+            lines: Default::default(),
+            shapes: vec![],
+        }
+    }
+}
+
+/// Retained content of a channel, kept until replaced by the next draw of the same channel
+#[derive(Default)]
+pub(crate) struct Channel {
+    /// Each channel have his own line meshes, so replacing it is just a matter of
+    /// overwriting the mesh buffers; one mesh for each [`LineKey`] ever drawn into the channel
+    pub lines: Vec<(LineKey, Line)>,
+    pub shapes: Vec<Entity>,
+    /// Mask of the last draw, the channel is hidden while it doesn't match the [`crate::Gizmos::mask`]
+    pub mask: u32,
+}
+
+impl Channel {
+    /// Line mesh used by the `key`, created on demand
+    pub fn line(
+        &mut self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        key: LineKey,
+    ) -> &Line {
+        let index = if let Some(index) = self.lines.iter().position(|(k, _)| *k == key) {
+            index
+        } else {
+            self.lines.push((key, Line::new(commands, meshes, key)));
+            self.lines.len() - 1
+        };
+        &self.lines[index].1
+    }
+}
@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+use smallvec::SmallVec;
+
+use crate::{line::LineTopology, GizmoStyle, GizmosContext};
+
+/// Maps scalar values in the `[0, 1]` range to colors, useful to visualize
+/// speed, heat, costs, and so on
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Colormap {
+    /// Perceptually uniform, from dark purple to yellow
+    Viridis,
+    /// Perceptually uniform, from black to light yellow passing through purple
+    Magma,
+    /// Rainbow like, but smoother than the classic jet colormap
+    Turbo,
+    /// Diverging, from blue to red with white in the middle
+    RedBlue,
+    /// Ten distinct colors, for values that aren't continuous use [`categorical`] instead
+    Categorical,
+}
+
+impl Colormap {
+    /// Samples the colormap, `t` is clamped to the `[0, 1]` range
+    pub fn sample(&self, t: f32) -> Color {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Colormap::Viridis => sample_table(&VIRIDIS, t),
+            Colormap::Magma => sample_table(&MAGMA, t),
+            Colormap::Turbo => turbo(t),
+            Colormap::RedBlue => sample_table(&RED_BLUE, t),
+            Colormap::Categorical => {
+                categorical(((t * CATEGORICAL.len() as f32) as usize).min(CATEGORICAL.len() - 1))
+            }
+        }
+    }
+
+    /// Samples the colormap with the `value` normalized by the `min` and `max`
+    pub fn map(&self, value: f32, min: f32, max: f32) -> Color {
+        let range = max - min;
+        if range.abs() > f32::EPSILON {
+            self.sample((value - min) / range)
+        } else {
+            self.sample(0.0)
+        }
+    }
+}
+
+/// Distinct color for each index, repeats after 10 colors
+pub fn categorical(index: usize) -> Color {
+    let [r, g, b] = CATEGORICAL[index % CATEGORICAL.len()];
+    Color::rgb_u8(r, g, b)
+}
+
+/// Screen space color bar showing how the values of a range are mapped by a [`Colormap`],
+/// see [`GizmosContext::colormap_legend`]
+///
+/// All sizes are in pixels
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoLegend {
+    pub colormap: Colormap,
+    /// Values mapped to the bottom and to the top of the bar
+    pub range: (f32, f32),
+    pub size: Vec2,
+    /// Number of ticks evenly spaced from the bottom to the top, both ends always have one
+    pub ticks: usize,
+    pub tick_size: f32,
+}
+
+impl Default for GizmoLegend {
+    fn default() -> Self {
+        Self {
+            colormap: Colormap::Viridis,
+            range: (0.0, 1.0),
+            size: Vec2::new(16.0, 128.0),
+            ticks: 3,
+            tick_size: 4.0,
+        }
+    }
+}
+
+/// Where each part of a [`GizmoLegend`] goes on the screen, useful to place the labels
+#[derive(Debug, Clone)]
+pub struct GizmoLegendLayout {
+    /// Bottom left corner of the bar
+    pub min: Vec2,
+    /// Top right corner of the bar
+    pub max: Vec2,
+    /// Outer end of each tick and the value it marks, from the bottom to the top
+    pub ticks: SmallVec<[(Vec2, f32); 8]>,
+}
+
+impl GizmoLegend {
+    /// Layout of the legend with the bottom left corner of the bar at the `anchor`
+    pub fn layout(&self, anchor: Vec2) -> GizmoLegendLayout {
+        let count = self.ticks.max(2);
+        let (min, max) = self.range;
+        let ticks = (0..count)
+            .map(|i| {
+                let t = i as f32 / (count - 1) as f32;
+                (
+                    anchor + Vec2::new(self.size.x + self.tick_size, t * self.size.y),
+                    min + (max - min) * t,
+                )
+            })
+            .collect();
+
+        GizmoLegendLayout {
+            min: anchor,
+            max: anchor + self.size,
+            ticks,
+        }
+    }
+}
+
+impl<'a> GizmosContext<'a> {
+    /// Draws the `legend` with the bottom left corner of the bar at the `anchor`, in pixels
+    /// from the bottom left corner of the screen, see [`GizmosContext::screen`]; the current
+    /// transform matrix is ignored, use [`GizmoLegend::layout`] to know where to place the labels
+    ///
+    /// **NOTE** The legend only lasts a single frame
+    pub fn colormap_legend(&mut self, anchor: Vec2, legend: &GizmoLegend) -> &mut Self {
+        const STEPS: usize = 32;
+
+        let layout = legend.layout(anchor);
+        let (w, h) = (legend.size.x, legend.size.y);
+        let frame = self.style().wireframe;
+        let width = self.style().line_width;
+
+        let style = GizmoStyle {
+            screen: true,
+            ..*self.style()
+        };
+        self.with_style(style, |context| {
+            let anchor = anchor.extend(0.0);
+            let arena = context.arena();
+
+            // Color bar
+            arena.offset_lines(
+                anchor,
+                (0..=STEPS).flat_map(|i| {
+                    let t = i as f32 / STEPS as f32;
+                    let color = legend.colormap.sample(t);
+                    [([0.0, t * h], color), ([w, t * h], color)]
+                }),
+                LineTopology::Segments,
+                // Keep the color bar solid
+                1.0,
+            );
+
+            // Frame and ticks
+            arena.offset_lines(
+                anchor,
+                [[0.0, 0.0], [w, 0.0], [w, h], [0.0, h]]
+                    .iter()
+                    .map(|offset| (*offset, frame)),
+                LineTopology::Loop,
+                width,
+            );
+            arena.offset_lines(
+                anchor,
+                layout
+                    .ticks
+                    .iter()
+                    .flat_map(|(end, _)| {
+                        let y = end.y - anchor.y;
+                        [[w, y], [end.x - anchor.x, y]]
+                    })
+                    .map(|offset| (offset, frame)),
+                LineTopology::Segments,
+                width,
+            );
+        })
+    }
+}
+
+/// Evenly spaced color stops (sRGB)
+fn sample_table(table: &[[u8; 3]], t: f32) -> Color {
+    let x = t * (table.len() - 1) as f32;
+    let i = (x as usize).min(table.len() - 2);
+    let f = x - i as f32;
+
+    let a = table[i];
+    let b = table[i + 1];
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f) / 255.0;
+    Color::rgb(lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2]))
+}
+
+/// Polynomial approximation of the turbo colormap
+fn turbo(t: f32) -> Color {
+    let r = 0.13572138
+        + t * (4.6153926 + t * (-42.660322 + t * (132.13108 + t * (-152.9424 + t * 59.28638))));
+    let g = 0.09140261
+        + t * (2.1941884 + t * (4.8429666 + t * (-14.185033 + t * (4.2772986 + t * 2.829566))));
+    let b = 0.1066733
+        + t * (12.641946 + t * (-60.582047 + t * (110.36277 + t * (-89.903109 + t * 27.34825))));
+    Color::rgb(
+        r.max(0.0).min(1.0),
+        g.max(0.0).min(1.0),
+        b.max(0.0).min(1.0),
+    )
+}
+
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [72, 40, 120],
+    [62, 74, 137],
+    [49, 104, 142],
+    [38, 130, 142],
+    [31, 158, 137],
+    [53, 183, 121],
+    [109, 205, 89],
+    [253, 231, 37],
+];
+
+const MAGMA: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 136, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+
+const RED_BLUE: [[u8; 3]; 5] = [
+    [5, 48, 97],
+    [67, 147, 195],
+    [247, 247, 247],
+    [214, 96, 77],
+    [103, 0, 31],
+];
+
+const CATEGORICAL: [[u8; 3]; 10] = [
+    [31, 119, 180],
+    [255, 127, 14],
+    [44, 160, 44],
+    [214, 39, 40],
+    [148, 103, 189],
+    [140, 86, 75],
+    [227, 119, 194],
+    [127, 127, 127],
+    [188, 189, 34],
+    [23, 190, 207],
+];
@@ -0,0 +1,213 @@
+use std::{
+    f32::consts::PI,
+    ops::{Deref, DerefMut},
+};
+
+use bevy::prelude::*;
+
+use crate::{line::LineTopology, GizmoDepth, GizmoLifetime, GizmosContext, MarkerStyle};
+
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Draws in the XY plane, for orthographic and sprite based games;
+/// every point is placed at the current z layer, so gizmos are sorted
+/// against each other and the sprites just like any other sprite
+///
+/// Styles and transforms are shared with the underlying [`GizmosContext`]
+///
+/// ```ignore
+/// gizmos.draw_2d(!0, |mut context| {
+///     context
+///         .with_z(10.0)
+///         .rect(Vec2::ZERO, Vec2::new(64.0, 32.0), 0.0)
+///         .arrow(Vec2::ZERO, Vec2::new(48.0, 0.0), 8.0, 0.0);
+/// });
+/// ```
+pub struct GizmosContext2d<'a> {
+    context: GizmosContext<'a>,
+    z: f32,
+}
+
+impl<'a> Deref for GizmosContext2d<'a> {
+    type Target = GizmosContext<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.context
+    }
+}
+
+impl<'a> DerefMut for GizmosContext2d<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.context
+    }
+}
+
+impl<'a> GizmosContext2d<'a> {
+    pub(crate) fn new(mut context: GizmosContext<'a>) -> Self {
+        context.with_depth(GizmoDepth::Layered);
+        Self { context, z: 0.0 }
+    }
+
+    /// Current z layer
+    #[inline]
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    /// Z layer of everything drawn next, higher layers are drawn on top
+    #[inline]
+    pub fn with_z(&mut self, z: f32) -> &mut Self {
+        self.z = z;
+        self
+    }
+
+    /// Connects each consecutive pair of points, same as [`GizmosContext2d::line_strip`]
+    #[inline]
+    pub fn line_list(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.line_strip(points, lifetime)
+    }
+
+    /// Connects each consecutive pair of points
+    #[inline]
+    pub fn line_strip(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let z = self.z;
+        self.context
+            .line_strip(points.into_iter().map(|p| p.extend(z)), lifetime);
+        self
+    }
+
+    /// Connects each consecutive pair of points and the last point with the first one
+    #[inline]
+    pub fn line_loop(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let z = self.z;
+        self.context
+            .line_loop(points.into_iter().map(|p| p.extend(z)), lifetime);
+        self
+    }
+
+    /// Disjoint lines
+    pub fn line_segments(
+        &mut self,
+        segments: &[(Vec2, Vec2)],
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let z = self.z;
+        self.context.lines(
+            segments
+                .iter()
+                .flat_map(|&(a, b)| [a.extend(z), b.extend(z)]),
+            LineTopology::Segments,
+            lifetime,
+        );
+        self
+    }
+
+    /// Closed polygon
+    #[inline]
+    pub fn polygon(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.line_loop(points, lifetime)
+    }
+
+    /// Axis aligned rectangle centered at `center`
+    pub fn rect(
+        &mut self,
+        center: Vec2,
+        size: Vec2,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let h = size * 0.5;
+        self.line_loop(
+            [
+                center + Vec2::new(-h.x, -h.y),
+                center + Vec2::new(h.x, -h.y),
+                center + Vec2::new(h.x, h.y),
+                center + Vec2::new(-h.x, h.y),
+            ]
+            .iter()
+            .copied(),
+            lifetime,
+        )
+    }
+
+    pub fn circle(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.line_loop(
+            (0..CIRCLE_SEGMENTS).map(|i| {
+                let t = (i as f32) * (2.0 / CIRCLE_SEGMENTS as f32);
+                let (y, x) = f32::sin_cos(t * PI);
+                center + Vec2::new(x, y) * radius
+            }),
+            lifetime,
+        )
+    }
+
+    /// Line from `from` to `to` with an arrow head at `to`, the head is `head_size` long
+    pub fn arrow(
+        &mut self,
+        from: Vec2,
+        to: Vec2,
+        head_size: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let delta = to - from;
+        let length = delta.length();
+        if length <= f32::EPSILON {
+            return self;
+        }
+
+        let back = delta / -length * head_size;
+        // Head sides at 30 degrees from the line
+        let (sin, cos) = f32::sin_cos(PI / 6.0);
+        let left = Vec2::new(back.x * cos - back.y * sin, back.x * sin + back.y * cos);
+        let right = Vec2::new(back.x * cos + back.y * sin, -back.x * sin + back.y * cos);
+
+        self.line_segments(&[(from, to), (to, to + left), (to, to + right)], lifetime)
+    }
+
+    /// Point with a fixed size in pixels
+    #[inline]
+    pub fn point(
+        &mut self,
+        position: Vec2,
+        size: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.marker(position, MarkerStyle::Square, size, lifetime)
+    }
+
+    /// Marker with a fixed size in pixels
+    #[inline]
+    pub fn marker(
+        &mut self,
+        position: Vec2,
+        style: MarkerStyle,
+        size: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let position = position.extend(self.z);
+        self.context.marker(position, style, size, lifetime);
+        self
+    }
+}
@@ -4,6 +4,15 @@ use bevy::{
 };
 use std::f32::consts::PI;
 
+/// Number of pre-generated level of detail meshes for each curved shape
+pub const LOD_COUNT: usize = 3;
+
+/// Segments of a full circle for each lod level, from the highest to the lowest quality
+pub const LOD_SEGMENTS: [u16; LOD_COUNT] = [64, 32, 16];
+
+/// Spherified cube divisions for each lod level
+pub const LOD_DIVISIONS: [u16; LOD_COUNT] = [8, 4, 2];
+
 pub fn wire_cube() -> Mesh {
     let mut color: Vec<[f32; 4]> = vec![];
     color.resize(8, [1.0; 4]);
@@ -46,43 +55,44 @@ pub fn cube() -> Mesh {
     mesh
 }
 
-pub fn wire_sphere() -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(32 * 3);
-    let mut indices: Vec<u16> = Vec::with_capacity(32 * 3 * 2);
+pub fn wire_sphere(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize * 3);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 3 * 2);
 
-    for i in 0..32u16 {
-        let t = (i as f32) * (2.0 / 32.0);
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, y, 0.0]);
-        if i < 31 {
+        if i < s - 1 {
             indices.push(i);
             indices.push(i + 1);
         }
     }
-    indices.push(31);
+    indices.push(s - 1);
     indices.push(0);
 
-    for i in 0..32u16 {
+    for i in 0..s {
         let p = positions[i as usize];
         positions.push([p[0], 0.0, p[1]]);
-        if i < 31 {
-            indices.push(i + 32);
-            indices.push(i + 1 + 32);
+        if i < s - 1 {
+            indices.push(i + s);
+            indices.push(i + 1 + s);
         }
     }
-    indices.push(31 + 32);
-    indices.push(32);
+    indices.push(s - 1 + s);
+    indices.push(s);
 
-    for i in 0..32u16 {
+    for i in 0..s {
         let p = positions[i as usize];
         positions.push([0.0, p[1], p[0]]);
-        if i < 31 {
-            indices.push(i + 64);
-            indices.push(i + 1 + 64);
+        if i < s - 1 {
+            indices.push(i + 2 * s);
+            indices.push(i + 1 + 2 * s);
         }
     }
-    indices.push(31 + 64);
-    indices.push(64);
+    indices.push(s - 1 + 2 * s);
+    indices.push(2 * s);
 
     let mut color: Vec<[f32; 4]> = vec![];
     color.resize(positions.len(), [1.0; 4]);
@@ -94,53 +104,87 @@ pub fn wire_sphere() -> Mesh {
     mesh
 }
 
-pub fn sphere() -> Mesh {
+pub fn sphere(divisions: u16) -> Mesh {
     helper::Sphere {
         hemisphere: false,
-        divisions: 4,
+        divisions,
     }
     .into()
 }
 
-pub fn wire_cylinder() -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(16 * 2);
-    let mut indices: Vec<u16> = Vec::with_capacity(16 * 2 * 2 + 2 * 4);
+/// **NOTE** `segments` must be a multiple of 4
+pub fn wire_cylinder(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize * 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 2 * 2 + 2 * 4);
 
     // Top
-    for i in 0..16u16 {
-        let t = (i as f32) * (2.0 / 16.0);
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, 0.5, y]);
     }
 
     // Bottom
-    for i in 0..16u16 {
+    for i in 0..s {
         let p = positions[i as usize];
         positions.push([p[0], -0.5, p[2]]);
     }
 
-    for i in 0..15u16 {
+    for i in 0..(s - 1) {
         indices.push(i);
         indices.push(i + 1);
-        indices.push(i + 16);
-        indices.push(i + 16 + 1);
+        indices.push(i + s);
+        indices.push(i + s + 1);
     }
-    indices.push(15);
+    indices.push(s - 1);
     indices.push(0);
-    indices.push(15 + 16);
-    indices.push(16);
+    indices.push(s - 1 + s);
+    indices.push(s);
 
-    indices.push(0);
-    indices.push(16);
+    // Bridges
+    for i in (0..s).step_by((s / 4) as usize) {
+        indices.push(i);
+        indices.push(s + i);
+    }
+
+    let mut color: Vec<[f32; 4]> = vec![];
+    color.resize(positions.len(), [1.0; 4]);
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, color);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_indices(Some(Indices::U16(indices)));
+    mesh
+}
 
-    indices.push(4);
-    indices.push(16 + 4);
+// TODO: Cylinder
 
-    indices.push(8);
-    indices.push(16 + 8);
+/// **NOTE** `segments` must be a multiple of 4
+pub fn wire_cone(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize + 1);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 2 + 4 * 2);
+
+    // Base
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
+        let (y, x) = f32::sin_cos(t * PI);
+        positions.push([x, -0.5, y]);
+        if i < s - 1 {
+            indices.push(i);
+            indices.push(i + 1);
+        }
+    }
+    indices.push(s - 1);
+    indices.push(0);
 
-    indices.push(12);
-    indices.push(16 + 12);
+    // Apex
+    positions.push([0.0, 0.5, 0.0]);
+    for i in (0..s).step_by((s / 4) as usize) {
+        indices.push(i);
+        indices.push(s);
+    }
 
     let mut color: Vec<[f32; 4]> = vec![];
     color.resize(positions.len(), [1.0; 4]);
@@ -152,40 +196,75 @@ pub fn wire_cylinder() -> Mesh {
     mesh
 }
 
-// TODO: Cylinder
+pub fn cone(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize + 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 6);
+
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
+        let (y, x) = f32::sin_cos(t * PI);
+        positions.push([x, -0.5, y]);
+    }
+    positions.push([0.0, 0.5, 0.0]); // Apex
+    positions.push([0.0, -0.5, 0.0]); // Base center
+
+    for i in 0..s {
+        let j = (i + 1) % s;
+        // Side
+        indices.push(i);
+        indices.push(s);
+        indices.push(j);
+        // Base
+        indices.push(i);
+        indices.push(j);
+        indices.push(s + 1);
+    }
+
+    let mut color: Vec<[f32; 4]> = vec![];
+    color.resize(positions.len(), [1.0; 4]);
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, color);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_indices(Some(Indices::U16(indices)));
+    mesh
+}
 
-pub fn wire_hemisphere() -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(16 * 3);
-    let mut indices: Vec<u16> = Vec::with_capacity(16 * 3 * 2);
+pub fn wire_hemisphere(segments: u16) -> Mesh {
+    let s = segments;
+    let h = s / 2;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize * 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 2 * 2);
 
-    for i in 0..32u16 {
-        let t = (i as f32) * (2.0 / 32.0);
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, 0.0, y]);
-        if i < 31 {
+        if i < s - 1 {
             indices.push(i);
             indices.push(i + 1);
         }
     }
-    indices.push(31);
+    indices.push(s - 1);
     indices.push(0);
 
-    for i in 0..16u16 {
-        let t = (i as f32) * (1.0 / 15.0);
+    for i in 0..h {
+        let t = (i as f32) * (1.0 / (h - 1) as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, y, 0.0]);
-        if i < 15 {
-            indices.push(i + 32);
-            indices.push(i + 1 + 32);
+        if i < h - 1 {
+            indices.push(i + s);
+            indices.push(i + 1 + s);
         }
     }
 
-    for i in 0..16u16 {
-        let p = positions[(i + 32) as usize];
+    for i in 0..h {
+        let p = positions[(i + s) as usize];
         positions.push([0.0, p[1], p[0]]);
-        if i < 15 {
-            indices.push(i + 48);
-            indices.push(i + 1 + 48);
+        if i < h - 1 {
+            indices.push(i + s + h);
+            indices.push(i + 1 + s + h);
         }
     }
 
@@ -199,34 +278,35 @@ pub fn wire_hemisphere() -> Mesh {
     mesh
 }
 
-pub fn hemisphere() -> Mesh {
+pub fn hemisphere(divisions: u16) -> Mesh {
     helper::Sphere {
         hemisphere: true,
-        divisions: 4,
+        divisions,
     }
     .into()
 }
 
-pub fn wire_capsule_cap() -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(16 * 2);
-    let mut indices: Vec<u16> = Vec::with_capacity(16 * 2 * 2);
+pub fn wire_capsule_cap(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize * 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 2 * 2);
 
-    for i in 0..16u16 {
-        let t = (i as f32) * (1.0 / 15.0);
+    for i in 0..s {
+        let t = (i as f32) * (1.0 / (s - 1) as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, y, 0.0]);
-        if i < 15 {
+        if i < s - 1 {
             indices.push(i);
             indices.push(i + 1)
         }
     }
 
-    for i in 0..16u16 {
+    for i in 0..s {
         let p = positions[i as usize];
         positions.push([0.0, p[1], p[0]]);
-        if i < 15 {
-            indices.push(i + 16);
-            indices.push(i + 16 + 1);
+        if i < s - 1 {
+            indices.push(i + s);
+            indices.push(i + s + 1);
         }
     }
 
@@ -240,35 +320,36 @@ pub fn wire_capsule_cap() -> Mesh {
     mesh
 }
 
-pub fn capsule_body() -> Mesh {
-    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(16 * 2);
-    let mut indices: Vec<u16> = Vec::with_capacity(15 * 6);
+pub fn capsule_body(segments: u16) -> Mesh {
+    let s = segments;
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(s as usize * 2);
+    let mut indices: Vec<u16> = Vec::with_capacity(s as usize * 6);
 
-    for i in 0..16u16 {
-        let t = (i as f32) * (2.0 / 16.0);
+    for i in 0..s {
+        let t = (i as f32) * (2.0 / s as f32);
         let (y, x) = f32::sin_cos(t * PI);
         positions.push([x, 0.5, y]);
     }
 
-    for i in 0..16u16 {
+    for i in 0..s {
         let p = positions[i as usize];
         positions.push([p[0], -0.5, p[2]]);
     }
 
-    for i in 0..15u16 {
+    for i in 0..(s - 1) {
         indices.push(i);
         indices.push(i + 1);
-        indices.push(i + 16);
+        indices.push(i + s);
         indices.push(i + 1);
-        indices.push(i + 16 + 1);
-        indices.push(i + 16);
+        indices.push(i + s + 1);
+        indices.push(i + s);
     }
-    indices.push(15);
+    indices.push(s - 1);
     indices.push(0);
-    indices.push(15 + 16);
+    indices.push(s - 1 + s);
     indices.push(0);
-    indices.push(16);
-    indices.push(15 + 16);
+    indices.push(s);
+    indices.push(s - 1 + s);
 
     let mut color: Vec<[f32; 4]> = vec![];
     color.resize(positions.len(), [1.0; 4]);
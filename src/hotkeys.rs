@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::{GizmoCategories, Gizmos};
+
+/// Key bindings used by the [`GizmoHotkeysPlugin`]
+pub struct GizmoHotkeys {
+    /// Each key toggles his mask bits, when the mask belongs to a registered category
+    /// the category is toggled instead, see [`GizmoCategories`]
+    pub bindings: Vec<(KeyCode, u32)>,
+    /// Shows or hides the overlay
+    pub overlay_key: Option<KeyCode>,
+    pub show_overlay: bool,
+    /// Font used by the overlay, which isn't displayed until a font is given
+    ///
+    /// **NOTE** Like any other Bevy UI the overlay needs an `UiCameraBundle` to be rendered
+    pub font: Handle<Font>,
+    pub font_size: f32,
+}
+
+impl Default for GizmoHotkeys {
+    fn default() -> Self {
+        let keys = [
+            KeyCode::F1,
+            KeyCode::F2,
+            KeyCode::F3,
+            KeyCode::F4,
+            KeyCode::F5,
+            KeyCode::F6,
+            KeyCode::F7,
+            KeyCode::F8,
+            KeyCode::F9,
+            KeyCode::F10,
+            KeyCode::F11,
+            KeyCode::F12,
+        ];
+
+        Self {
+            bindings: keys
+                .iter()
+                .enumerate()
+                .map(|(bit, key)| (*key, 1 << bit))
+                .collect(),
+            overlay_key: None,
+            show_overlay: true,
+            font: Default::default(),
+            font_size: 16.0,
+        }
+    }
+}
+
+/// Toggles the gizmos mask bits with the keyboard and lists the state of each binding on screen
+#[derive(Default)]
+pub struct GizmoHotkeysPlugin;
+
+impl Plugin for GizmoHotkeysPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<GizmoHotkeys>()
+            .init_resource::<GizmoCategories>();
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.add_startup_system(gizmos_overlay_setup.system())
+            .add_system(gizmos_hotkeys_system.system())
+            .add_system(gizmos_overlay_system.system());
+    }
+}
+
+/// Marks the overlay text
+struct GizmoOverlay;
+
+fn gizmos_hotkeys_system(
+    keys: Res<Input<KeyCode>>,
+    mut hotkeys: ResMut<GizmoHotkeys>,
+    mut gizmos: ResMut<Gizmos>,
+    mut categories: ResMut<GizmoCategories>,
+) {
+    if let Some(overlay_key) = hotkeys.overlay_key {
+        if keys.just_pressed(overlay_key) {
+            hotkeys.show_overlay = !hotkeys.show_overlay;
+        }
+    }
+
+    for (key, mask) in hotkeys.bindings.iter() {
+        if !keys.just_pressed(*key) {
+            continue;
+        }
+
+        if let Some(category) = categories.iter_mut().find(|c| c.mask == *mask) {
+            // Keep the category in sync, the mask will be updated by the categories
+            category.enabled = !category.enabled;
+        } else {
+            gizmos.mask ^= *mask;
+        }
+    }
+}
+
+fn gizmos_overlay_setup(mut commands: Commands) {
+    commands
+        .spawn()
+        .insert_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(8.0),
+                    left: Val::Px(8.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GizmoOverlay);
+}
+
+/// Rebuilds the overlay text when any binding changes his state
+fn gizmos_overlay_system(
+    hotkeys: Res<GizmoHotkeys>,
+    gizmos: Res<Gizmos>,
+    categories: Res<GizmoCategories>,
+    mut last_mask: Local<Option<u32>>,
+    mut query: Query<(&mut Text, &mut Visible), With<GizmoOverlay>>,
+) {
+    let changed = hotkeys.is_changed() || categories.is_changed();
+    if !changed && *last_mask == Some(gizmos.mask) {
+        return;
+    }
+    *last_mask = Some(gizmos.mask);
+
+    for (mut text, mut visible) in query.iter_mut() {
+        visible.is_visible = hotkeys.show_overlay;
+
+        text.sections = hotkeys
+            .bindings
+            .iter()
+            .map(|(key, mask)| {
+                let category = categories.iter().find(|c| c.mask == *mask);
+                let enabled = (gizmos.mask & mask) != 0;
+
+                let (name, color) = if let Some(category) = category {
+                    (category.name.clone(), category.color)
+                } else {
+                    (format!("mask {:#x}", mask), Color::WHITE)
+                };
+
+                TextSection {
+                    value: format!(
+                        "{:?} {} [{}]\n",
+                        key,
+                        name,
+                        if enabled { "on" } else { "off" }
+                    ),
+                    style: TextStyle {
+                        font: hotkeys.font.clone(),
+                        font_size: hotkeys.font_size,
+                        color: if enabled { color } else { Color::GRAY },
+                    },
+                }
+            })
+            .collect();
+    }
+}
@@ -1,21 +1,59 @@
 #![allow(dead_code)]
 
-use std::{f32::consts::PI, fmt::Debug, ops::Range};
+use std::{borrow::Borrow, f32::consts::PI, fmt::Debug, ops::Range, sync::Mutex};
 
 use bevy::{
+    ecs::schedule::ShouldRun,
     prelude::*,
-    render::{pipeline::RenderPipeline, render_graph::base, shader},
+    render::{camera::RenderLayers, pipeline::RenderPipeline, shader},
+    utils::HashMap,
 };
 use smallvec::SmallVec;
 
+mod aabb;
+mod annotation;
+mod arena;
+mod camera;
+mod category;
+mod channel;
+mod colormap;
+mod context2d;
 mod gen;
+mod hotkeys;
+mod lifetime;
 mod line;
+mod lod;
+mod markers;
 mod material;
 mod mesh_helper;
+mod picking;
+mod plot;
 mod render_graph;
-
-use line::Line;
+mod silhouette;
+
+pub use aabb::{Aabb, AabbGizmosConfig, AabbGizmosPlugin};
+pub use annotation::{GizmoAnnotation, GizmoAnnotationLayout, GizmoProjection};
+use arena::{LineArena, LineArenas, LineBuffers};
+pub use camera::GizmoCamera;
+pub use category::{GizmoCategories, GizmoCategory};
+use channel::{Channel, ChannelDraw};
+pub use colormap::{categorical, Colormap, GizmoLegend, GizmoLegendLayout};
+pub use context2d::GizmosContext2d;
+pub use hotkeys::{GizmoHotkeys, GizmoHotkeysPlugin};
+pub use lifetime::{gizmos_fixed_tick_system, GizmoClock, GizmoLifetime, GizmoTime};
+use lifetime::{Expiry, GizmoClocks};
+pub use line::LineTopology;
+use line::{Line, LineKey};
+pub use lod::GizmoQuality;
+use lod::{GizmoLod, LodMeshes, DEFAULT_LOD};
+pub use markers::MarkerStyle;
 pub use material::GizmoMaterial;
+pub use picking::{GizmoClicked, GizmoHovered, GizmoPicking, GizmoPickingPlugin};
+pub use plot::GizmoPlot;
+use plot::PlotHistory;
+pub use render_graph::{add_gizmo_pass, GizmoPass, GizmoPassTarget, GIZMO_PASS};
+pub use silhouette::GizmoSilhouettes;
+use silhouette::Silhouette;
 
 #[derive(Debug, Copy, Clone)]
 pub enum Axis {
@@ -24,6 +62,44 @@ pub enum Axis {
     Z,
 }
 
+impl Axis {
+    #[inline]
+    pub fn direction(&self) -> Vec3 {
+        match self {
+            Axis::X => Vec3::X,
+            Axis::Y => Vec3::Y,
+            Axis::Z => Vec3::Z,
+        }
+    }
+}
+
+/// Linear interpolation between two colors, in linear space
+pub(crate) fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let a = a.as_rgba_linear();
+    let b = b.as_rgba_linear();
+    Color::rgba_linear(
+        a.r() + (b.r() - a.r()) * t,
+        a.g() + (b.g() - a.g()) * t,
+        a.b() + (b.b() - a.b()) * t,
+        a.a() + (b.a() - a.a()) * t,
+    )
+}
+
+/// Shortest rotation that takes the `from` direction into the `to` direction,
+/// both must be normalized
+pub(crate) fn rotation_between(from: Vec3, to: Vec3) -> Quat {
+    let d = from.dot(to);
+    if d > 1.0 - f32::EPSILON {
+        Quat::IDENTITY
+    } else if d < -1.0 + f32::EPSILON {
+        // Opposite directions, rotate around any perpendicular axis
+        let other = if from.x.abs() < 0.9 { Vec3::X } else { Vec3::Z };
+        Quat::from_axis_angle(from.cross(other).normalize(), PI)
+    } else {
+        Quat::from_axis_angle(from.cross(to).normalize(), d.acos())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum GizmoShape {
     /// Similar to blender empty axis, his solid shape is a octahedron
@@ -51,6 +127,11 @@ pub enum GizmoShape {
         radius: f32,
         height: f32,
     },
+    /// Base centered at `-height * 0.5` and apex at `height * 0.5` in the Y axis
+    Cone {
+        radius: f32,
+        height: f32,
+    },
     Capsule {
         radius: f32,
         /// Height of the cylindrical portion, the total height is given by `height + 2.0 * radius`
@@ -76,6 +157,11 @@ pub struct Gizmo {
     pub wireframe: Color,
     /// **NOTE** Not every gizmo has a filled shape, so this might be ignored
     pub color: Color,
+    /// The gizmo is only visible when his mask matches the [`Gizmos::mask`],
+    /// see [`GizmoCategories`]
+    pub mask: u32,
+    /// Cameras that will see the gizmo, every camera by default
+    pub layers: RenderLayers,
 }
 
 impl Default for Gizmo {
@@ -86,6 +172,8 @@ impl Default for Gizmo {
             },
             wireframe: Color::WHITE,
             color: Color::rgba_linear(0.0, 0.0, 0.0, 0.0),
+            mask: u32::MAX,
+            layers: RenderLayers::all(),
         }
     }
 }
@@ -98,35 +186,70 @@ pub struct GizmoBundle {
     pub gizmo: Gizmo,
 }
 
+/// How gizmos deal with the scene depth
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GizmoDepth {
+    /// Hidden behind other objects, like any other mesh
+    Test,
+    /// Always drawn on top
+    Overlay,
+    /// Sorted by the z coordinate, like sprites are in a 2D scene, see [`GizmosContext2d`]
+    Layered,
+}
+
+impl Default for GizmoDepth {
+    fn default() -> Self {
+        GizmoDepth::Test
+    }
+}
+
+impl GizmoDepth {
+    fn render_pipelines(&self) -> RenderPipelines {
+        let handle = match self {
+            GizmoDepth::Test => render_graph::GIZMOS_PIPELINE_HANDLE,
+            GizmoDepth::Overlay => render_graph::GIZMOS_OVERLAY_PIPELINE_HANDLE,
+            GizmoDepth::Layered => render_graph::GIZMOS_2D_PIPELINE_HANDLE,
+        };
+        RenderPipelines::from_pipelines(vec![RenderPipeline::new(handle.typed())])
+    }
+}
+
 /// The gizmo may use multiple [`GizmosMeshBundles`] to render it self
 #[derive(Bundle)]
 pub(crate) struct GizmoMeshBundle {
     pub mesh: Handle<Mesh>,
-    pub main_pass: base::MainPass, // TODO: GizmoPass
+    /// Gizmos are drawn by the gizmo pass of each [`GizmoCamera`], use the `render_layers` to pick which cameras
+    pub gizmo_pass: GizmoPass,
     pub draw: Draw,
     pub visible: Visible,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub material: GizmoMaterial,
+    pub render_layers: RenderLayers,
 }
 
 impl Default for GizmoMeshBundle {
     fn default() -> Self {
+        Self::with_depth(GizmoDepth::Test)
+    }
+}
+
+impl GizmoMeshBundle {
+    pub fn with_depth(depth: GizmoDepth) -> Self {
         Self {
-            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
-                render_graph::GIZMOS_PIPELINE_HANDLE.typed(),
-            )]),
+            render_pipelines: depth.render_pipelines(),
             mesh: Default::default(),
             visible: Visible {
                 is_visible: true,
                 is_transparent: true,
             },
-            main_pass: Default::default(),
+            gizmo_pass: Default::default(),
             draw: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
             material: Default::default(),
+            render_layers: RenderLayers::all(),
         }
     }
 }
@@ -136,37 +259,119 @@ impl Default for GizmoMeshBundle {
 /// Defines a single frame gizmo command
 pub enum GizmoCommand {
     Shape {
-        transform: Transform,
+        /// Full matrix, shear and non-uniform scales that a [`Transform`] can't hold are kept
+        transform: Mat4,
         shape: GizmoShape,
-        duration: f32,
+        lifetime: GizmoLifetime,
         color: Color,
         wireframe: Color,
+        depth: GizmoDepth,
+        /// Cameras that will see it
+        layers: RenderLayers,
     },
     // TODO: Mesh, rendered with a custom wireframe material
-    LineList {
-        // TODO: Having a long set of points will allocate memory every frame,
-        // having multiple `LineList` also allocate memory, because each command
-        // are kept as a SegQueue node that lives in the heap;
-        //
-        // Pick your poison until a better solution come around, just go with
-        // the more convenient solution for your problem;
+    // ? NOTE: Line and marker commands are only used by those that last more than a single frame, immediate
+    // ? ones are written straight into the context arena and never allocate once warmed up
+    /// Lines connecting the `points` as told by their `topology`
+    Lines {
         points: SmallVec<[Vec3; 4]>,
-        duration: f32,
+        topology: LineTopology,
+        lifetime: GizmoLifetime,
         color: Color,
+        /// Per point colors, when empty `color` is used instead
+        colors: SmallVec<[Color; 4]>,
+        /// Width in pixels
+        width: f32,
+        depth: GizmoDepth,
+        layers: RenderLayers,
+        /// Points are in pixels, see [`GizmosContext::screen`]
+        screen: bool,
     },
+    /// Screen constant sized marker
+    Marker {
+        position: Vec3,
+        style: MarkerStyle,
+        /// Size in pixels
+        size: f32,
+        lifetime: GizmoLifetime,
+        color: Color,
+        /// Line width in pixels
+        width: f32,
+        depth: GizmoDepth,
+        layers: RenderLayers,
+        screen: bool,
+    },
+    /// Removes every gizmo drawn with [`GizmoLifetime::UntilCleared`] using this `id`
+    Clear { id: u32 },
+}
+
+impl GizmoCommand {
+    /// Line meshes used by the command, if any
+    fn line_key(&self) -> Option<LineKey> {
+        match self {
+            GizmoCommand::Lines {
+                depth,
+                layers,
+                screen,
+                ..
+            }
+            | GizmoCommand::Marker {
+                depth,
+                layers,
+                screen,
+                ..
+            } => Some(LineKey {
+                depth: *depth,
+                layers: *layers,
+                screen: *screen,
+            }),
+            GizmoCommand::Shape { .. } | GizmoCommand::Clear { .. } => None,
+        }
+    }
+}
+
+/// Global switch for every gizmo
+pub struct GizmoConfig {
+    /// When disabled nothing is drawn, every gizmo is hidden and the gizmo passes are skipped
+    ///
+    /// **NOTE** For release builds use the `disabled` cargo feature instead,
+    /// it compiles all the drawing code away
+    pub enabled: bool,
+}
+
+impl Default for GizmoConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
 }
 
 pub struct Gizmos {
     /// Control which set of gizmos it will draw
     pub mask: u32,
-    commands: crossbeam::queue::SegQueue<GizmoCommand>,
+    /// Copied from the [`GizmoConfig`]
+    enabled: bool,
+    /// Each command is paired with the fixed tick count when it was drawn
+    commands: crossbeam::queue::SegQueue<(u64, GizmoCommand)>,
+    /// See [`gizmos_fixed_tick_system`]
+    pub(crate) fixed_tick: u64,
+    /// Immediate mode lines
+    arenas: LineArenas,
+    /// Channels drawn since the last update
+    channels: crossbeam::queue::SegQueue<ChannelDraw>,
+    /// History of each plot, see [`GizmosContext::plot`]
+    plots: Mutex<HashMap<String, PlotHistory>>,
 }
 
 impl Default for Gizmos {
     fn default() -> Self {
         Gizmos {
             mask: u32::MAX,
+            enabled: true,
             commands: Default::default(),
+            fixed_tick: 0,
+            arenas: Default::default(),
+            channels: Default::default(),
+            plots: Default::default(),
         }
     }
 }
@@ -177,35 +382,199 @@ impl Gizmos {
     /// to control it;
     #[inline]
     pub fn draw(&self, mask: u32, scope: impl FnOnce(GizmosContext)) -> &Self {
-        if (mask & self.mask) != 0 {
+        if self.is_enabled() && (mask & self.mask) != 0 {
             (scope)(GizmosContext::new(self));
         }
         self
     }
+
+    /// Same as [`Gizmos::draw`] but draws in 2D, see [`GizmosContext2d`]
+    #[inline]
+    pub fn draw_2d(&self, mask: u32, scope: impl FnOnce(GizmosContext2d)) -> &Self {
+        self.draw(mask, |context| (scope)(GizmosContext2d::new(context)))
+    }
+
+    /// False when disabled by the [`GizmoConfig`] or by the `disabled` cargo feature
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        !cfg!(feature = "disabled") && self.enabled
+    }
+
+    /// Same as [`Gizmos::draw`] but uses the `category` mask and color
+    #[inline]
+    pub fn draw_category(
+        &self,
+        category: &GizmoCategory,
+        scope: impl FnOnce(GizmosContext),
+    ) -> &Self {
+        self.draw(category.mask, |mut context| {
+            context.with_wireframe(category.color);
+            (scope)(context)
+        })
+    }
+
+    /// Draws into the channel `name`, replacing everything previously drawn in it; the channel
+    /// content lasts until replaced or cleared, so it only needs to be drawn when it changes
+    ///
+    /// The channel is hidden while his `mask` doesn't match the [`Gizmos::mask`], but unlike
+    /// [`Gizmos::draw`] the `scope` still runs, so the channel is up to date once shown again
+    ///
+    /// **NOTE** Lifetimes are ignored, also raw commands aren't part of the channel,
+    /// see [`GizmosContext::command`]
+    #[inline]
+    pub fn channel(&self, name: &str, mask: u32, scope: impl FnOnce(GizmosContext)) -> &Self {
+        if !self.is_enabled() {
+            return self;
+        }
+
+        let mut context = GizmosContext::new(self);
+        context.channel = Some(ChannelDraw::new(name, mask));
+        (scope)(context);
+        self
+    }
+
+    /// Removes everything drawn in the channel `name`
+    #[inline]
+    pub fn clear_channel(&self, name: &str) -> &Self {
+        if !self.is_enabled() {
+            return self;
+        }
+
+        self.channels.push(ChannelDraw::new(name, u32::MAX));
+        self
+    }
+
+    /// Removes every gizmo drawn with [`GizmoLifetime::UntilCleared`] using this `id`,
+    /// gizmos drawn after this call are kept
+    #[inline]
+    pub fn clear(&self, id: u32) -> &Self {
+        if !self.is_enabled() {
+            return self;
+        }
+
+        self.commands
+            .push((self.fixed_tick, GizmoCommand::Clear { id }));
+        self
+    }
 }
 
-// TODO: Will be wholesome if we could select each gizmos like if they where a button
+/// Transform placing the Y axis along the segment `a` to `b`, centered in the middle of it,
+/// also returns the segment length
+fn segment_transform(a: Vec3, b: Vec3) -> (Transform, f32) {
+    let delta = b - a;
+    let length = delta.length();
+    let rotation = if length > f32::EPSILON {
+        rotation_between(Vec3::Y, delta / length)
+    } else {
+        Quat::IDENTITY
+    };
+    (
+        Transform {
+            translation: (a + b) * 0.5,
+            rotation,
+            scale: Vec3::ONE,
+        },
+        length,
+    )
+}
+
+/// Drawing settings of a [`GizmosContext`], see [`GizmosContext::with_style`]
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoStyle {
+    /// Fill color
+    pub color: Color,
+    /// Wireframe and lines color
+    pub wireframe: Color,
+    /// Lines and markers width in pixels
+    pub line_width: f32,
+    pub depth: GizmoDepth,
+    /// When set, replaces the lifetime given to each draw call
+    pub lifetime: Option<GizmoLifetime>,
+    /// Cameras that will see the gizmos
+    pub layers: RenderLayers,
+    /// Lines and markers are in pixels, see [`GizmosContext::screen`]
+    pub screen: bool,
+}
+
+impl Default for GizmoStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba_linear(0.0, 0.0, 0.0, 0.0),
+            wireframe: Color::WHITE,
+            line_width: 1.0,
+            depth: GizmoDepth::Test,
+            lifetime: None,
+            layers: RenderLayers::all(),
+            screen: false,
+        }
+    }
+}
+
+// TODO: Immediate mode gizmos can't be picked, only the persistent ones, see `GizmoPickingPlugin`
 
 pub struct GizmosContext<'a> {
-    color: Color,
-    wireframe: Color,
-    stack: Vec<Transform>,
+    style: GizmoStyle,
+    /// Styles saved by [`GizmosContext::push_style`]
+    style_stack: Vec<GizmoStyle>,
+    /// Each matrix is already multiplied by his parent
+    stack: Vec<Mat4>,
     command_buffer: &'a Gizmos,
+    /// Taken from the command buffer by the first immediate line
+    arena: Option<LineArena>,
+    /// Set when drawing into a channel, see [`Gizmos::channel`]
+    channel: Option<ChannelDraw>,
+}
+
+impl<'a> Drop for GizmosContext<'a> {
+    fn drop(&mut self) {
+        if let Some(arena) = self.arena.take() {
+            self.command_buffer.arenas.give(arena);
+        }
+
+        if let Some(channel) = self.channel.take() {
+            self.command_buffer.channels.push(channel);
+        }
+    }
 }
 
 impl<'a> GizmosContext<'a> {
     fn new(command_buffer: &'a Gizmos) -> Self {
         Self {
-            color: Color::rgba_linear(0.0, 0.0, 0.0, 0.0),
-            wireframe: Color::WHITE,
+            style: GizmoStyle::default(),
+            style_stack: vec![],
             stack: vec![],
             command_buffer,
+            arena: None,
+            channel: None,
         }
     }
 
+    /// Current transform matrix
+    #[inline]
+    pub fn matrix(&self) -> Mat4 {
+        self.stack.last().copied().unwrap_or(Mat4::IDENTITY)
+    }
+
+    /// Pushes `transform` relative to the current matrix
     #[inline]
     pub fn push_matrix(&mut self, transform: Transform) -> &mut Self {
-        self.stack.push(transform);
+        self.push_mat4(transform.compute_matrix())
+    }
+
+    /// Same as [`GizmosContext::push_matrix`] but takes a full matrix, so shear
+    /// and non-uniform scales are kept, for both lines and shapes
+    #[inline]
+    pub fn push_mat4(&mut self, matrix: Mat4) -> &mut Self {
+        let matrix = self.matrix() * matrix;
+        self.stack.push(matrix);
+        self
+    }
+
+    /// Pushes `transform` as is, ignoring the current matrix,
+    /// just like [`GizmosContext::push_matrix`] it must be popped later
+    #[inline]
+    pub fn set_matrix(&mut self, transform: Transform) -> &mut Self {
+        self.stack.push(transform.compute_matrix());
         self
     }
 
@@ -215,63 +584,513 @@ impl<'a> GizmosContext<'a> {
         self
     }
 
+    /// Draws the `scope` with `transform` pushed, popping it right after
+    #[inline]
+    pub fn with_matrix(
+        &mut self,
+        transform: Transform,
+        scope: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        self.push_matrix(transform);
+        (scope)(self);
+        self.pop_matrix()
+    }
+
+    /// Same as [`GizmosContext::with_matrix`] but takes a full matrix
+    #[inline]
+    pub fn with_mat4(&mut self, matrix: Mat4, scope: impl FnOnce(&mut Self)) -> &mut Self {
+        self.push_mat4(matrix);
+        (scope)(self);
+        self.pop_matrix()
+    }
+
+    /// Current style
+    #[inline]
+    pub fn style(&self) -> &GizmoStyle {
+        &self.style
+    }
+
+    /// Saves the current style and replaces it with `style`, restore it with [`GizmosContext::pop_style`]
+    #[inline]
+    pub fn push_style(&mut self, style: GizmoStyle) -> &mut Self {
+        self.style_stack
+            .push(std::mem::replace(&mut self.style, style));
+        self
+    }
+
+    /// Restores the style saved by the last [`GizmosContext::push_style`]
+    #[inline]
+    pub fn pop_style(&mut self) -> &mut Self {
+        if let Some(style) = self.style_stack.pop() {
+            self.style = style;
+        }
+        self
+    }
+
+    /// Draws the `scope` with `style`, restoring the current style right after;
+    /// use `GizmoStyle { line_width: 2.0, ..*ctx.style() }` to only tweak a few settings
+    #[inline]
+    pub fn with_style(&mut self, style: GizmoStyle, scope: impl FnOnce(&mut Self)) -> &mut Self {
+        self.push_style(style);
+        (scope)(self);
+        self.pop_style()
+    }
+
     #[inline]
     pub fn with_color(&mut self, color: Color) -> &mut Self {
-        self.color = color;
+        self.style.color = color;
         self
     }
 
     #[inline]
     pub fn no_color(&mut self) -> &mut Self {
-        self.color = Color::rgba_linear(0.0, 0.0, 0.0, 0.0);
+        self.style.color = Color::rgba_linear(0.0, 0.0, 0.0, 0.0);
         self
     }
 
     #[inline]
     pub fn with_wireframe(&mut self, color: Color) -> &mut Self {
-        self.wireframe = color;
+        self.style.wireframe = color;
         self
     }
 
     #[inline]
     pub fn no_wireframe(&mut self) -> &mut Self {
-        self.wireframe = Color::rgba_linear(0.0, 0.0, 0.0, 0.0);
+        self.style.wireframe = Color::rgba_linear(0.0, 0.0, 0.0, 0.0);
+        self
+    }
+
+    /// Lines and markers width in pixels
+    #[inline]
+    pub fn with_line_width(&mut self, width: f32) -> &mut Self {
+        self.style.line_width = width;
+        self
+    }
+
+    #[inline]
+    pub fn with_depth(&mut self, depth: GizmoDepth) -> &mut Self {
+        self.style.depth = depth;
+        self
+    }
+
+    /// Only cameras with any of the `layers` will see the gizmos drawn next
+    #[inline]
+    pub fn with_layers(&mut self, layers: RenderLayers) -> &mut Self {
+        self.style.layers = layers;
+        self
+    }
+
+    /// Lines and markers drawn next are in pixels, from the bottom left corner of the screen,
+    /// they ignore the camera and are drawn on top of the scene; the transform matrix still applies,
+    /// use [`GizmosContext::world`] to go back
+    ///
+    /// **NOTE** Pixels are physical, while [`Window::cursor_position`] and the UI are logical,
+    /// multiply them by [`Window::scale_factor`] first, e.g. to draw at the cursor on HiDPI screens
+    ///
+    /// **NOTE** Shapes are always drawn in world space
+    #[inline]
+    pub fn screen(&mut self) -> &mut Self {
+        self.style.screen = true;
         self
     }
 
-    pub fn shape(&mut self, shape: GizmoShape, duration: f32) -> &mut Self {
-        self.command(GizmoCommand::Shape {
-            transform: self.stack.last().copied().unwrap_or_default(),
+    /// Lines and markers drawn next are in world space, the default
+    #[inline]
+    pub fn world(&mut self) -> &mut Self {
+        self.style.screen = false;
+        self
+    }
+
+    /// Replaces the lifetime given to each draw call, `None` goes back to the given lifetimes
+    #[inline]
+    pub fn with_lifetime(&mut self, lifetime: Option<GizmoLifetime>) -> &mut Self {
+        self.style.lifetime = lifetime;
+        self
+    }
+
+    /// Lifetime of a draw call, overridden by the current style
+    #[inline]
+    fn lifetime(&self, lifetime: impl Into<GizmoLifetime>) -> GizmoLifetime {
+        self.style.lifetime.unwrap_or_else(|| lifetime.into())
+    }
+
+    /// Immediate lines for the current depth and layers, or the channel lines
+    fn arena(&mut self) -> &mut LineBuffers {
+        let key = LineKey {
+            depth: self.style.depth,
+            layers: self.style.layers,
+            screen: self.style.screen,
+        };
+        if let Some(channel) = &mut self.channel {
+            return channel.lines.batch(key);
+        }
+
+        let command_buffer = self.command_buffer;
+        self.arena
+            .get_or_insert_with(|| command_buffer.arenas.take())
+            .batch(key)
+    }
+
+    #[inline]
+    pub fn shape(&mut self, shape: GizmoShape, lifetime: impl Into<GizmoLifetime>) -> &mut Self {
+        self.shape_local(Transform::identity(), shape, lifetime)
+    }
+
+    /// Axis aligned box, from `min` to `max`
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, lifetime: impl Into<GizmoLifetime>) -> &mut Self {
+        self.obb(
+            (min + max) * 0.5,
+            (max - min) * 0.5,
+            Quat::IDENTITY,
+            lifetime,
+        )
+    }
+
+    /// Oriented box
+    pub fn obb(
+        &mut self,
+        center: Vec3,
+        half_extents: Vec3,
+        rotation: Quat,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let local = Transform {
+            translation: center,
+            rotation,
+            scale: Vec3::ONE,
+        };
+        self.shape_local(
+            local,
+            GizmoShape::Cube {
+                size: half_extents * 2.0,
+            },
+            lifetime,
+        )
+    }
+
+    /// Capsule with the cylindrical portion going from `a` to `b`
+    pub fn capsule_between(
+        &mut self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let (local, height) = segment_transform(a, b);
+        self.shape_local(
+            local,
+            GizmoShape::Capsule {
+                radius,
+                height,
+                axis: Axis::Y,
+            },
+            lifetime,
+        )
+    }
+
+    pub fn cylinder_between(
+        &mut self,
+        a: Vec3,
+        b: Vec3,
+        radius: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let (local, height) = segment_transform(a, b);
+        self.shape_local(local, GizmoShape::Cylinder { radius, height }, lifetime)
+    }
+
+    /// Cone with the base centered at `base` and his tip at `apex`
+    pub fn cone_between(
+        &mut self,
+        base: Vec3,
+        apex: Vec3,
+        radius: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let (local, height) = segment_transform(base, apex);
+        self.shape_local(local, GizmoShape::Cone { radius, height }, lifetime)
+    }
+
+    /// Pushes a shape placed by `local` relative to the current transform matrix
+    fn shape_local(
+        &mut self,
+        local: Transform,
+        shape: GizmoShape,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let command = GizmoCommand::Shape {
+            transform: self.matrix() * local.compute_matrix(),
             shape,
-            duration,
-            wireframe: self.wireframe,
-            color: self.color,
-        })
+            lifetime: self.lifetime(lifetime),
+            wireframe: self.style.wireframe,
+            color: self.style.color,
+            depth: self.style.depth,
+            layers: self.style.layers,
+        };
+
+        if let Some(channel) = &mut self.channel {
+            channel.shapes.push(command);
+            self
+        } else {
+            self.command(command)
+        }
     }
 
+    /// Connects each consecutive pair of points, same as [`GizmosContext::line_strip`];
+    /// takes points by value or by reference, like `&[Vec3]` or `&Vec<Vec3>`
+    #[inline]
     pub fn line_list(
         &mut self,
-        points: impl Into<SmallVec<[Vec3; 4]>>,
-        duration: f32,
+        points: impl IntoIterator<Item = impl Borrow<Vec3>>,
+        lifetime: impl Into<GizmoLifetime>,
     ) -> &mut Self {
-        let mut points = points.into();
+        self.line_strip(points, lifetime)
+    }
 
-        // Transform points before pushing the line list
-        if let Some(transform) = self.stack.last() {
-            points.iter_mut().for_each(|p| *p = transform.mul_vec3(*p));
+    /// Connects each consecutive pair of points
+    #[inline]
+    pub fn line_strip(
+        &mut self,
+        points: impl IntoIterator<Item = impl Borrow<Vec3>>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.lines(
+            points.into_iter().map(|p| *p.borrow()),
+            LineTopology::Strip,
+            lifetime,
+        )
+    }
+
+    /// Connects each consecutive pair of points and the last point with the first one
+    #[inline]
+    pub fn line_loop(
+        &mut self,
+        points: impl IntoIterator<Item = impl Borrow<Vec3>>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.lines(
+            points.into_iter().map(|p| *p.borrow()),
+            LineTopology::Loop,
+            lifetime,
+        )
+    }
+
+    /// Disjoint lines
+    #[inline]
+    pub fn line_segments(
+        &mut self,
+        segments: &[(Vec3, Vec3)],
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.lines(
+            segments.iter().flat_map(|&(a, b)| [a, b]),
+            LineTopology::Segments,
+            lifetime,
+        )
+    }
+
+    /// Sets the wireframe color from a scalar `value` normalized by the `min` and `max`
+    #[inline]
+    pub fn with_wireframe_scalar(
+        &mut self,
+        value: f32,
+        min: f32,
+        max: f32,
+        colormap: Colormap,
+    ) -> &mut Self {
+        self.with_wireframe(colormap.map(value, min, max))
+    }
+
+    /// Same as [`GizmosContext::line_list`] but with a color for each point;
+    /// points without a color use the current wireframe color and any extra color is ignored
+    #[inline]
+    pub fn line_list_colored(
+        &mut self,
+        points: impl IntoIterator<Item = impl Borrow<Vec3>>,
+        colors: impl IntoIterator<Item = Color>,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let wireframe = self.style.wireframe;
+        let mut colors = colors.into_iter();
+        self.lines_colored(
+            points
+                .into_iter()
+                .map(|p| (*p.borrow(), colors.next().unwrap_or(wireframe))),
+            LineTopology::Strip,
+            lifetime,
+        )
+    }
+
+    /// Line strip colored from `start` to `end`
+    pub fn line_gradient(
+        &mut self,
+        points: &[Vec3],
+        start: Color,
+        end: Color,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let last = points.len().saturating_sub(1).max(1) as f32;
+        self.lines_colored(
+            points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (*p, lerp_color(start, end, i as f32 / last))),
+            LineTopology::Strip,
+            lifetime,
+        )
+    }
+
+    /// Line strip colored by the `values` of each point, see [`Colormap::map`]
+    pub fn line_scalar(
+        &mut self,
+        points: &[Vec3],
+        values: &[f32],
+        min: f32,
+        max: f32,
+        colormap: Colormap,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.lines_colored(
+            points
+                .iter()
+                .zip(values)
+                .map(|(p, v)| (*p, colormap.map(*v, min, max))),
+            LineTopology::Strip,
+            lifetime,
+        )
+    }
+
+    fn lines(
+        &mut self,
+        points: impl IntoIterator<Item = Vec3>,
+        topology: LineTopology,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let color = self.style.wireframe;
+        self.push_lines(
+            points.into_iter().map(|p| (p, color)),
+            true,
+            topology,
+            lifetime,
+        )
+    }
+
+    fn lines_colored(
+        &mut self,
+        points: impl IntoIterator<Item = (Vec3, Color)>,
+        topology: LineTopology,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.push_lines(points, false, topology, lifetime)
+    }
+
+    /// Pushes lines, `uniform` tells if every point has the same color (the current wireframe color)
+    fn push_lines(
+        &mut self,
+        points: impl IntoIterator<Item = (Vec3, Color)>,
+        uniform: bool,
+        topology: LineTopology,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        // Transform points before pushing the lines
+        let matrix = self.matrix();
+        let points = points
+            .into_iter()
+            .map(|(p, c)| (matrix.transform_point3(p), c));
+
+        let lifetime = self.lifetime(lifetime);
+        let width = self.style.line_width;
+        let depth = self.style.depth;
+        let layers = self.style.layers;
+        let screen = self.style.screen;
+
+        if !lifetime.is_immediate() && self.channel.is_none() {
+            let color = self.style.wireframe;
+            let (points, colors): (SmallVec<_>, SmallVec<_>) = if uniform {
+                (points.map(|(p, _)| p).collect(), SmallVec::new())
+            } else {
+                points.unzip()
+            };
+            self.command(GizmoCommand::Lines {
+                points,
+                topology,
+                lifetime,
+                color,
+                colors,
+                width,
+                depth,
+                layers,
+                screen,
+            })
+        } else {
+            self.arena().lines(points, topology, width);
+            self
+        }
+    }
+
+    /// Point with a fixed size in pixels, regardless of his distance to the camera
+    #[inline]
+    pub fn point(
+        &mut self,
+        position: Vec3,
+        size: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        self.marker(position, MarkerStyle::Square, size, lifetime)
+    }
+
+    /// Marker with a fixed size in pixels, regardless of his distance to the camera
+    pub fn marker(
+        &mut self,
+        position: Vec3,
+        style: MarkerStyle,
+        size: f32,
+        lifetime: impl Into<GizmoLifetime>,
+    ) -> &mut Self {
+        let position = self.matrix().transform_point3(position);
+        let lifetime = self.lifetime(lifetime);
+
+        // Same as the lines, immediate markers go straight into the arena
+        if lifetime.is_immediate() || self.channel.is_some() {
+            let (color, width) = (self.style.wireframe, self.style.line_width);
+            markers::append_marker(
+                &mut self.arena().edit(),
+                position,
+                style,
+                size,
+                width,
+                color,
+            );
+            return self;
         }
 
-        self.command(GizmoCommand::LineList {
-            points,
-            duration,
-            color: self.wireframe,
-        })
+        self.command(GizmoCommand::Marker {
+            position,
+            style,
+            size,
+            lifetime,
+            color: self.style.wireframe,
+            width: self.style.line_width,
+            depth: self.style.depth,
+            layers: self.style.layers,
+            screen: self.style.screen,
+        })
+    }
+
+    /// See [`Gizmos::clear`]
+    #[inline]
+    pub fn clear(&mut self, id: u32) -> &mut Self {
+        self.command_buffer.clear(id);
+        self
     }
 
-    /// **NOTE** Pushes a raw command, ignoring the current transform matrix
+    /// **NOTE** Pushes a raw command, ignoring the current transform matrix and style
     #[inline]
     pub fn command(&mut self, gizmo: GizmoCommand) -> &mut Self {
-        self.command_buffer.commands.push(gizmo);
+        let command_buffer = self.command_buffer;
+        command_buffer
+            .commands
+            .push((command_buffer.fixed_tick, gizmo));
         self
     }
 }
@@ -285,11 +1104,12 @@ struct GizmosMeshes {
     mesh_empty: Handle<Mesh>,
     mesh_billboard: Handle<Mesh>,
     mesh_cube: Handle<Mesh>,
-    mesh_sphere: Handle<Mesh>,
-    mesh_hemisphere: Handle<Mesh>,
-    mesh_cylinder: Handle<Mesh>,
-    mesh_capsule_body: Handle<Mesh>,
-    mesh_capsule_cap: Handle<Mesh>, // Similar to hemisphere but with less redundant lines
+    mesh_sphere: LodMeshes,
+    mesh_hemisphere: LodMeshes,
+    mesh_cylinder: LodMeshes,
+    mesh_cone: LodMeshes,
+    mesh_capsule_body: LodMeshes,
+    mesh_capsule_cap: LodMeshes, // Similar to hemisphere but with less redundant lines
 }
 
 #[derive(Default)]
@@ -300,18 +1120,60 @@ struct GizmosResources {
     // instances: HashMap<Entity, SmallVec<[Entity; 4]>>,
 
     // Gizmos command buffer
+    clocks: GizmoClocks,
     /// Volatile gizmos shapes
-    shapes_volatile_tracker: Vec<(f32, Entity)>,
+    shapes_volatile_tracker: Vec<(Expiry, Entity)>,
+    /// Shared line meshes, one batch for each [`LineKey`] ever drawn
+    lines: Vec<LineBatch>,
+    /// Retained content of each channel, see [`Gizmos::channel`]
+    channels: HashMap<String, Channel>,
+    /// Commands waiting to be processed, kept around to reuse his capacity
+    pending: Vec<(u64, GizmoCommand)>,
+}
+
+impl GizmosResources {
+    /// Index of the line batch used by the `key`, created on demand
+    fn line_batch(
+        &mut self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        key: LineKey,
+    ) -> usize {
+        if let Some(index) = line_index(&self.lines, key) {
+            return index;
+        }
+
+        self.lines.push(LineBatch {
+            key,
+            volatile: Line::new(commands, meshes, key),
+            volatile_tracker: vec![],
+            immediate: Line::new(commands, meshes, key),
+        });
+        self.lines.len() - 1
+    }
+}
+
+#[inline]
+fn line_index(lines: &[LineBatch], key: LineKey) -> Option<usize> {
+    lines.iter().position(|batch| batch.key == key)
+}
+
+/// Lines that share the same meshes
+struct LineBatch {
+    key: LineKey,
     /// Not quite immediate mode but they will disappear eventually,
     /// it's particular hard to manage these lines because they share a single
     /// mesh
-    lines_volatile: Line,
-    lines_volatile_tracker: Vec<(f32, Range<usize>, Range<usize>)>,
+    volatile: Line,
+    volatile_tracker: VolatileLines,
     /// This set of lines will only be active once per frame which
     /// make their management way cheaper;
-    lines_immediate: Line,
+    immediate: Line,
 }
 
+/// Expiry, vertices and indices of each volatile line
+type VolatileLines = Vec<(Expiry, Range<usize>, Range<usize>)>;
+
 fn gizmos_setup(
     mut commands: Commands,
     mut gizmos: ResMut<GizmosResources>,
@@ -323,33 +1185,52 @@ fn gizmos_setup(
     gizmos.meshes_wireframe.mesh_empty = meshes.add(gen::wire_empty());
     //gizmos.meshes_wireframe.mesh_billboard = ...; // Empty
     gizmos.meshes_wireframe.mesh_cube = meshes.add(gen::wire_cube());
-    gizmos.meshes_wireframe.mesh_sphere = meshes.add(gen::wire_sphere());
-    gizmos.meshes_wireframe.mesh_hemisphere = meshes.add(gen::wire_hemisphere());
-    gizmos.meshes_wireframe.mesh_cylinder = meshes.add(gen::wire_cylinder());
-    gizmos.meshes_wireframe.mesh_capsule_body = gizmos.meshes_wireframe.mesh_cylinder.clone();
-    gizmos.meshes_wireframe.mesh_capsule_cap = meshes.add(gen::wire_capsule_cap());
 
     gizmos.meshes.wireframe = false;
     gizmos.meshes.mesh_empty = meshes.add(gen::empty());
     gizmos.meshes.mesh_billboard = meshes.add(gen::billboard());
     gizmos.meshes.mesh_cube = meshes.add(gen::cube());
-    gizmos.meshes.mesh_sphere = meshes.add(gen::sphere());
-    gizmos.meshes.mesh_hemisphere = meshes.add(gen::hemisphere());
-    // gizmos.meshes.mesh_cylinder = meshes.add(gen::cylinder());
-    gizmos.meshes.mesh_capsule_body = meshes.add(gen::capsule_body());
-    gizmos.meshes.mesh_capsule_cap = gizmos.meshes.mesh_hemisphere.clone();
 
-    // Shared line mesh
-    gizmos.lines_volatile = Line::new(&mut commands, meshes);
-    gizmos.lines_immediate = Line::new(&mut commands, meshes);
+    // Curved shapes
+    for lod in 0..gen::LOD_COUNT {
+        let segments = gen::LOD_SEGMENTS[lod];
+        let divisions = gen::LOD_DIVISIONS[lod];
+
+        let wireframe = &mut gizmos.meshes_wireframe;
+        wireframe.mesh_sphere[lod] = meshes.add(gen::wire_sphere(segments));
+        wireframe.mesh_hemisphere[lod] = meshes.add(gen::wire_hemisphere(segments));
+        wireframe.mesh_cylinder[lod] = meshes.add(gen::wire_cylinder(segments / 2));
+        wireframe.mesh_cone[lod] = meshes.add(gen::wire_cone(segments / 2));
+        wireframe.mesh_capsule_body[lod] = wireframe.mesh_cylinder[lod].clone();
+        wireframe.mesh_capsule_cap[lod] = meshes.add(gen::wire_capsule_cap(segments / 2));
+
+        let solid = &mut gizmos.meshes;
+        solid.mesh_sphere[lod] = meshes.add(gen::sphere(divisions));
+        solid.mesh_hemisphere[lod] = meshes.add(gen::hemisphere(divisions));
+        // solid.mesh_cylinder[lod] = meshes.add(gen::cylinder(segments / 2));
+        solid.mesh_cone[lod] = meshes.add(gen::cone(segments / 2));
+        solid.mesh_capsule_body[lod] = meshes.add(gen::capsule_body(segments / 2));
+        solid.mesh_capsule_cap[lod] = solid.mesh_hemisphere[lod].clone();
+    }
+
+    // Shared line mesh, other layers are created when first drawn
+    for depth in [GizmoDepth::Test, GizmoDepth::Overlay].iter().copied() {
+        let key = LineKey {
+            depth,
+            layers: RenderLayers::all(),
+            screen: false,
+        };
+        gizmos.line_batch(&mut commands, meshes, key);
+    }
 }
 
 fn gizmos_update_system(
     mut commands: Commands,
     time: Res<Time>,
+    gizmo_time: Res<GizmoTime>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut gizmos: ResMut<GizmosResources>,
-    gizmos_command_buffer: ResMut<Gizmos>,
+    mut gizmos_command_buffer: ResMut<Gizmos>,
     gizmos_query: Query<(Entity, &Gizmo, &Children), (Changed<Gizmo>,)>,
     // gizmos_removed_query: Query<Entity, Without<Gizmo>>,
 ) {
@@ -373,13 +1254,19 @@ fn gizmos_update_system(
             commands.entity(entity).despawn();
         });
 
+        let visible =
+            gizmos_command_buffer.is_enabled() && (gizmo.mask & gizmos_command_buffer.mask) != 0;
+
         if gizmo.wireframe.a() > f32::EPSILON {
             gizmo_instantiate(
                 &mut commands,
                 &gizmos.meshes_wireframe,
                 entity,
                 gizmo.shape.clone(),
-                gizmo.wireframe,
+                GizmoMaterial::from(gizmo.wireframe),
+                GizmoDepth::Test,
+                gizmo.layers,
+                visible,
             );
         }
 
@@ -389,53 +1276,411 @@ fn gizmos_update_system(
                 &gizmos.meshes,
                 entity,
                 gizmo.shape.clone(),
-                gizmo.color,
+                GizmoMaterial::from(gizmo.color),
+                GizmoDepth::Test,
+                gizmo.layers,
+                visible,
             );
         }
     }
 
+    // Replace the content of each channel drawn since the last update
+    while let Some(mut draw) = gizmos_command_buffer.channels.pop() {
+        let mut channel = gizmos.channels.remove(&draw.name).unwrap_or_default();
+        channel.mask = draw.mask;
+        let visible =
+            gizmos_command_buffer.is_enabled() && (draw.mask & gizmos_command_buffer.mask) != 0;
+
+        for entity in channel.shapes.drain(..) {
+            commands.entity(entity).despawn_recursive();
+        }
+        for command in draw.shapes.drain(..) {
+            if let GizmoCommand::Shape {
+                transform,
+                shape,
+                color,
+                wireframe,
+                depth,
+                layers,
+                ..
+            } = command
+            {
+                let entity = spawn_shape(
+                    &mut commands,
+                    gizmos,
+                    transform,
+                    shape,
+                    color,
+                    wireframe,
+                    depth,
+                    layers,
+                    visible,
+                );
+                channel.shapes.push(entity);
+            }
+        }
+
+        // Lines drawn with other keys in the previous draw must be cleared too
+        for (_, line) in channel.lines.iter() {
+            let mut edit = line.edit(meshes);
+            edit.vertices.clear();
+            edit.colors.clear();
+            edit.offsets.clear();
+            edit.indices.clear();
+        }
+        for (key, buffers) in draw.lines.batches() {
+            buffers.flush(&mut channel.line(&mut commands, meshes, key).edit(meshes));
+        }
+
+        gizmos.channels.insert(draw.name, channel);
+    }
+
+    // Every line batch must exist before fetching the shared meshes,
+    // so the commands are drained first to know which batches they need
+    let mut pending = std::mem::take(&mut gizmos.pending);
+    pending.extend(std::iter::from_fn(|| gizmos_command_buffer.commands.pop()));
+
+    let mut keys: SmallVec<[LineKey; 4]> = SmallVec::new();
+    gizmos_command_buffer
+        .arenas
+        .for_each(|arena| keys.extend(arena.batches().map(|(key, _)| key)));
+    keys.extend(pending.iter().filter_map(|(_, command)| command.line_key()));
+    for key in keys {
+        gizmos.line_batch(&mut commands, meshes, key);
+    }
+
     // Manage previous volatile gizmos
+    gizmos
+        .clocks
+        .advance(&gizmo_time, &time, gizmos_command_buffer.fixed_tick);
+    let clocks = &gizmos.clocks;
 
     // Manage gizmos shapes
     // TODO: Recycle entities to improve performance
-    for i in (0..gizmos.shapes_volatile_tracker.len()).rev() {
-        let (time_left, _) = &mut gizmos.shapes_volatile_tracker[i];
+    remove_volatile_shapes(
+        &mut commands,
+        &mut gizmos.shapes_volatile_tracker,
+        |expiry| clocks.is_expired(expiry),
+    );
+
+    let mut lines_immediate_edits: SmallVec<[mesh_helper::MeshEditXC; 4]> = gizmos
+        .lines
+        .iter()
+        .map(|batch| {
+            // SAFETY: Each mesh is fetched only here,
+            // further more the `meshes` won't mutate only his meshes
+            let meshes = unsafe { &mut *(meshes as *mut Assets<Mesh>) };
+            batch.immediate.edit(meshes)
+        })
+        .collect();
 
-        if *time_left < 0.0 {
-            let (_, entity) = gizmos.shapes_volatile_tracker.remove(i);
-            commands.entity(entity).despawn_recursive();
-        } else {
-            *time_left -= time.delta_seconds();
+    // Clear right away the immediate mode lines since they are just one frame
+    for edit in lines_immediate_edits.iter_mut() {
+        edit.vertices.clear();
+        edit.colors.clear();
+        edit.offsets.clear();
+        edit.indices.clear();
+    }
+
+    // Immediate lines drawn since the last update
+    let lines = &gizmos.lines;
+    gizmos_command_buffer.arenas.for_each(|arena| {
+        for (key, buffers) in arena.batches() {
+            // Batch created above
+            let index = line_index(lines, key).unwrap();
+            buffers.flush(&mut lines_immediate_edits[index]);
+        }
+    });
+
+    let mut lines_volatile_edits: SmallVec<[Option<mesh_helper::MeshEditXC>; 4]> =
+        gizmos.lines.iter().map(|_| None).collect();
+
+    // Manage volatile lines
+    for (batch, edit) in gizmos.lines.iter_mut().zip(lines_volatile_edits.iter_mut()) {
+        remove_volatile_lines(
+            &mut batch.volatile_tracker,
+            edit,
+            &batch.volatile,
+            meshes,
+            |expiry| clocks.is_expired(expiry),
+        );
+    }
+
+    for (fixed_tick, command) in pending.drain(..) {
+        match command {
+            GizmoCommand::Shape {
+                transform,
+                shape,
+                lifetime,
+                color,
+                wireframe,
+                depth,
+                layers,
+            } => {
+                let entity = spawn_shape(
+                    &mut commands,
+                    gizmos,
+                    transform,
+                    shape,
+                    color,
+                    wireframe,
+                    depth,
+                    layers,
+                    true,
+                );
+
+                // Keep track
+                let expiry = gizmos.clocks.expiry(lifetime, fixed_tick);
+                gizmos.shapes_volatile_tracker.push((expiry, entity));
+            }
+            GizmoCommand::Lines {
+                points,
+                topology,
+                lifetime,
+                color,
+                colors,
+                width,
+                depth,
+                layers,
+                screen,
+            } => {
+                let key = LineKey {
+                    depth,
+                    layers,
+                    screen,
+                };
+
+                // True if more than a single frame
+                let volatile = !lifetime.is_immediate();
+
+                // Add new lines, batch created above
+                let index = line_index(&gizmos.lines, key).unwrap();
+                let edit = if volatile {
+                    lines_volatile_edits[index].get_or_insert_with(|| {
+                        // SAFETY: This mesh is fetched once, further more the `meshes` won't mutate only his meshes
+                        let meshes = unsafe { &mut *(meshes as *mut _) };
+                        gizmos.lines[index].volatile.edit(meshes)
+                    })
+                } else {
+                    &mut lines_immediate_edits[index]
+                };
+
+                let v = edit.vertices.len();
+                let inserted_points = points.len();
+
+                // SAFETY: `Vec3` can be trivially interpreted as `[f32; 3]`, and transmute guarantees both
+                // types have the same size so the `SmallVec` buffer will always have the right amount of points
+                unsafe {
+                    edit.vertices
+                        .extend(std::mem::transmute::<_, SmallVec<[[f32; 3]; 4]>>(points));
+                }
+
+                if colors.is_empty() {
+                    edit.colors
+                        .resize(edit.colors.len() + inserted_points, <[f32; 4]>::from(color));
+                } else {
+                    // Repeat the last color for any missing point
+                    let last = *colors.last().unwrap();
+                    edit.colors.extend(
+                        colors
+                            .into_iter()
+                            .chain(std::iter::repeat(last))
+                            .take(inserted_points)
+                            .map(<[f32; 4]>::from),
+                    );
+                }
+                edit.offsets
+                    .resize(edit.offsets.len() + inserted_points, [0.0; 2]);
+
+                let i = edit.indices.len();
+                edit.lines()
+                    .connect(v as u32, inserted_points as u32, topology, width);
+
+                if volatile {
+                    // Keep track, but only if volatile
+                    let expiry = gizmos.clocks.expiry(lifetime, fixed_tick);
+                    gizmos.lines[index].volatile_tracker.push((
+                        expiry,
+                        Range {
+                            start: v,
+                            end: edit.vertices.len(),
+                        },
+                        Range {
+                            start: i,
+                            end: edit.indices.len(),
+                        },
+                    ));
+                }
+            }
+            GizmoCommand::Marker {
+                position,
+                style,
+                size,
+                lifetime,
+                color,
+                width,
+                depth,
+                layers,
+                screen,
+            } => {
+                // True if more than a single frame
+                let volatile = !lifetime.is_immediate();
+
+                // Batch created above
+                let key = LineKey {
+                    depth,
+                    layers,
+                    screen,
+                };
+                let index = line_index(&gizmos.lines, key).unwrap();
+                let edit = if volatile {
+                    lines_volatile_edits[index].get_or_insert_with(|| {
+                        // SAFETY: This mesh is fetched once, further more the `meshes` won't mutate only his meshes
+                        let meshes = unsafe { &mut *(meshes as *mut _) };
+                        gizmos.lines[index].volatile.edit(meshes)
+                    })
+                } else {
+                    &mut lines_immediate_edits[index]
+                };
+
+                let v = edit.vertices.len();
+                let i = edit.indices.len();
+
+                markers::append_marker(&mut edit.lines(), position, style, size, width, color);
+
+                if volatile {
+                    // Keep track, but only if volatile
+                    let expiry = gizmos.clocks.expiry(lifetime, fixed_tick);
+                    gizmos.lines[index].volatile_tracker.push((
+                        expiry,
+                        Range {
+                            start: v,
+                            end: edit.vertices.len(),
+                        },
+                        Range {
+                            start: i,
+                            end: edit.indices.len(),
+                        },
+                    ));
+                }
+            }
+            GizmoCommand::Clear { id } => {
+                let cleared = |expiry: &Expiry| *expiry == Expiry::Cleared(id);
+
+                remove_volatile_shapes(&mut commands, &mut gizmos.shapes_volatile_tracker, cleared);
+                for (batch, edit) in gizmos.lines.iter_mut().zip(lines_volatile_edits.iter_mut()) {
+                    remove_volatile_lines(
+                        &mut batch.volatile_tracker,
+                        edit,
+                        &batch.volatile,
+                        meshes,
+                        cleared,
+                    );
+                }
+            }
         }
     }
 
-    let mut lines_immediate_edit: mesh_helper::MeshEditXC = {
-        // SAFETY: This mesh is fetched only here,
-        // further more the `meshes` won't mutate only his meshes
-        let meshes = unsafe { &mut *(meshes as *mut _) };
-        gizmos.lines_immediate.edit(meshes)
+    gizmos.pending = pending;
+}
+
+/// Spawns a shape gizmo, the shape entity is returned
+#[allow(clippy::too_many_arguments)]
+fn spawn_shape(
+    commands: &mut Commands,
+    gizmos: &GizmosResources,
+    transform: Mat4,
+    shape: GizmoShape,
+    color: Color,
+    wireframe: Color,
+    depth: GizmoDepth,
+    layers: RenderLayers,
+    visible: bool,
+) -> Entity {
+    // Whatever the `Transform` can't hold (shear and non-uniform scales under rotation)
+    // is left to the material, applied in world space on top of the model matrix
+    let trs = Transform::from_matrix(transform);
+    let deform = transform * trs.compute_matrix().inverse();
+    let material = |color: Color| {
+        if deform.abs_diff_eq(Mat4::IDENTITY, 1e-4) {
+            GizmoMaterial::from(color)
+        } else {
+            GizmoMaterial {
+                deform: true,
+                deform_matrix: deform,
+                ..GizmoMaterial::from(color)
+            }
+        }
     };
 
-    // Clear right away the immediate mode lines since they are just one frame
-    lines_immediate_edit.vertices.clear();
-    lines_immediate_edit.colors.clear();
-    lines_immediate_edit.indices.clear();
+    let entity = commands
+        .spawn()
+        .insert_bundle((trs, GlobalTransform::default(), Children::default()))
+        .id();
+
+    if wireframe.a() > f32::EPSILON {
+        gizmo_instantiate(
+            commands,
+            &gizmos.meshes_wireframe,
+            entity,
+            shape.clone(),
+            material(wireframe),
+            depth,
+            layers,
+            visible,
+        );
+    }
+
+    if color.a() > f32::EPSILON {
+        gizmo_instantiate(
+            commands,
+            &gizmos.meshes,
+            entity,
+            shape,
+            material(color),
+            depth,
+            layers,
+            visible,
+        );
+    }
 
-    let mut lines_volatile_edit: Option<mesh_helper::MeshEditXC> = None;
+    entity
+}
 
-    // Manage volatile lines
-    for i in (0..gizmos.lines_volatile_tracker.len()).rev() {
-        let (time_left, _, _) = &mut gizmos.lines_volatile_tracker[i];
+/// Despawns the volatile shapes that are `expired`
+fn remove_volatile_shapes(
+    commands: &mut Commands,
+    tracker: &mut Vec<(Expiry, Entity)>,
+    expired: impl Fn(&Expiry) -> bool,
+) {
+    tracker.retain(|(expiry, entity)| {
+        if (expired)(expiry) {
+            commands.entity(*entity).despawn_recursive();
+            false
+        } else {
+            true
+        }
+    });
+}
 
-        if *time_left < 0.0 {
-            let (_, v_range, i_range) = gizmos.lines_volatile_tracker.remove(i);
+/// Removes the `expired` volatile lines from their shared mesh, the mesh is only fetched when needed
+fn remove_volatile_lines<'a>(
+    tracker: &mut VolatileLines,
+    edit: &mut Option<mesh_helper::MeshEditXC<'a>>,
+    line: &Line,
+    meshes: &mut Assets<Mesh>,
+    expired: impl Fn(&Expiry) -> bool,
+) {
+    for i in (0..tracker.len()).rev() {
+        if (expired)(&tracker[i].0) {
+            let (_, v_range, i_range) = tracker.remove(i);
 
             // Remove lines
-            let edit = lines_volatile_edit.get_or_insert_with(|| {
+            let edit = edit.get_or_insert_with(|| {
                 // SAFETY: This mesh is fetched once,
                 // further more the `meshes` won't mutate only his meshes
                 let meshes = unsafe { &mut *(meshes as *mut _) };
-                gizmos.lines_volatile.edit(meshes)
+                line.edit(meshes)
             });
 
             // ? NOTE: This algorithm will reduce the amount of memory that needs to be sended over to the GPU
@@ -443,6 +1688,7 @@ fn gizmos_update_system(
             // Remove vertex attributes
             edit.vertices.drain(v_range.start..v_range.end);
             edit.colors.drain(v_range.start..v_range.end);
+            edit.offsets.drain(v_range.start..v_range.end);
 
             let i_offset = i_range.end - i_range.start;
             let v_offset = v_range.end - v_range.start;
@@ -468,7 +1714,7 @@ fn gizmos_update_system(
                 .resize_with(edit.indices.len() - i_offset, || unreachable!());
 
             // Offset each other volatile line to keep track the moved parts
-            for (_, v, i) in gizmos.lines_volatile_tracker.iter_mut() {
+            for (_, v, i) in tracker.iter_mut() {
                 if v.start >= v_range.start {
                     v.start -= v_offset;
                     v.end -= v_offset;
@@ -478,105 +1724,69 @@ fn gizmos_update_system(
                     i.end -= i_offset;
                 }
             }
-        } else {
-            *time_left -= time.delta_seconds();
         }
     }
+}
 
-    while let Some(command) = gizmos_command_buffer.commands.pop() {
-        match command {
-            GizmoCommand::Shape {
-                transform,
-                shape,
-                duration,
-                color,
-                wireframe,
-            } => {
-                // Spawn gizmo
-                let entity = commands
-                    .spawn().insert_bundle((transform, GlobalTransform::default(), Children::default()))
-                    .id();
-
-                let gizmo = Gizmo {
-                    shape,
-                    color,
-                    wireframe,
-                };
-
-                if gizmo.wireframe.a() > f32::EPSILON {
-                    gizmo_instantiate(
-                        &mut commands,
-                        &gizmos.meshes_wireframe,
-                        entity,
-                        gizmo.shape.clone(),
-                        gizmo.wireframe,
-                    );
-                }
+fn gizmos_config_system(config: Res<GizmoConfig>, mut gizmos: ResMut<Gizmos>) {
+    if config.is_changed() {
+        gizmos.enabled = config.enabled;
+    }
+}
 
-                if gizmo.color.a() > f32::EPSILON {
-                    gizmo_instantiate(
-                        &mut commands,
-                        &gizmos.meshes,
-                        entity,
-                        gizmo.shape.clone(),
-                        gizmo.color,
-                    );
-                }
+/// Runs the gizmos systems while enabled by the [`GizmoConfig`], and once more
+/// right after it changes, so every gizmo gets hidden or shown
+fn gizmos_enabled_criteria(config: Res<GizmoConfig>) -> ShouldRun {
+    if config.enabled || config.is_changed() {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
 
-                // Keep track
-                gizmos.shapes_volatile_tracker.push((duration, entity));
+/// Shows or hides the gizmos when the [`Gizmos::mask`] or the [`GizmoConfig`] changes
+fn gizmos_visibility_system(
+    gizmos: Res<Gizmos>,
+    resources: Res<GizmosResources>,
+    mut last_state: Local<Option<(u32, bool)>>,
+    gizmos_query: Query<(&Gizmo, &Children)>,
+    children_query: Query<&Children>,
+    mut visible_query: Query<&mut Visible, With<GizmoMaterial>>,
+) {
+    let state = (gizmos.mask, gizmos.is_enabled());
+    if *last_state != Some(state) {
+        // Every gizmo mesh, including the shared line meshes
+        if last_state.map_or(true, |(_, enabled)| enabled != state.1) {
+            for mut visible in visible_query.iter_mut() {
+                visible.is_visible = state.1;
             }
-            GizmoCommand::LineList {
-                points,
-                duration,
-                color,
-            } => {
-                // True if more than a single frame
-                let volatile = duration > f32::EPSILON;
-
-                // Add new lines
-                let edit = if volatile {
-                    lines_volatile_edit.get_or_insert_with(|| {
-                        // SAFETY: This mesh is fetched once, further more the `meshes` won't mutate only his meshes
-                        let meshes = unsafe { &mut *(meshes as *mut _) };
-                        gizmos.lines_volatile.edit(meshes)
-                    })
-                } else {
-                    &mut lines_immediate_edit
-                };
-
-                let v = edit.vertices.len();
-                let inserted_points = points.len();
-
-                // SAFETY: `Vec3` can be trivially interpreted as `[f32; 3]`, and transmute guarantees both
-                // types have the same size so the `SmallVec` buffer will always have the right amount of points
-                unsafe {
-                    edit.vertices
-                        .extend(std::mem::transmute::<_, SmallVec<[[f32; 3]; 4]>>(points));
-                }
-
-                edit.colors
-                    .resize(edit.colors.len() + inserted_points, <[f32; 4]>::from(color));
+        }
+        *last_state = Some(state);
 
-                let i = edit.indices.len();
-                for index in 0..(inserted_points - 1) {
-                    edit.indices.push((v + index) as u32);
-                    edit.indices.push((v + index) as u32 + 1);
+        for (gizmo, children) in gizmos_query.iter() {
+            let is_visible = gizmos.is_enabled() && (gizmo.mask & gizmos.mask) != 0;
+            for child in children.iter() {
+                if let Ok(mut visible) = visible_query.get_mut(*child) {
+                    visible.is_visible = is_visible;
                 }
+            }
+        }
+    }
 
-                if volatile {
-                    // Keep track, but only if volatile
-                    gizmos.lines_volatile_tracker.push((
-                        duration,
-                        Range {
-                            start: v,
-                            end: edit.vertices.len(),
-                        },
-                        Range {
-                            start: i,
-                            end: edit.indices.len(),
-                        },
-                    ));
+    // Channels can be redrawn with a different mask at any time, so they are checked every frame
+    for channel in resources.channels.values() {
+        let is_visible = gizmos.is_enabled() && (channel.mask & gizmos.mask) != 0;
+        let lines = channel.lines.iter().filter_map(|(_, line)| line.entity());
+        let shapes = channel
+            .shapes
+            .iter()
+            .filter_map(|entity| children_query.get(*entity).ok())
+            .flat_map(|children| children.iter().copied());
+
+        for entity in lines.chain(shapes) {
+            if let Ok(mut visible) = visible_query.get_mut(entity) {
+                if visible.is_visible != is_visible {
+                    visible.is_visible = is_visible;
                 }
             }
         }
@@ -584,14 +1794,25 @@ fn gizmos_update_system(
 }
 
 /// Instantiates a gizmo mesh
+#[allow(clippy::too_many_arguments)]
 fn gizmo_instantiate(
     commands: &mut Commands,
     gizmos: &GizmosMeshes,
     parent: Entity,
     gizmo_shape: GizmoShape,
-    gizmo_color: Color,
+    mut material: GizmoMaterial,
+    depth: GizmoDepth,
+    layers: RenderLayers,
+    visible: bool,
 ) {
-    let mut material = GizmoMaterial::from(gizmo_color);
+    let bundle = || GizmoMeshBundle {
+        visible: Visible {
+            is_visible: visible,
+            is_transparent: true,
+        },
+        render_layers: layers,
+        ..GizmoMeshBundle::with_depth(depth)
+    };
     //material.lit = !gizmos.wireframe;
 
     match gizmo_shape {
@@ -601,7 +1822,7 @@ fn gizmo_instantiate(
                     transform: Transform::from_scale(Vec3::splat(radius)),
                     mesh: gizmos.mesh_empty.clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
                 .insert(Parent(parent));
         }
@@ -616,7 +1837,7 @@ fn gizmo_instantiate(
                     transform: Transform::default(),
                     mesh: gizmos.mesh_billboard.clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
                 .insert(Parent(parent));
         }
@@ -626,7 +1847,7 @@ fn gizmo_instantiate(
                     transform: Transform::from_scale(size),
                     mesh: gizmos.mesh_cube.clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
                 .insert(Parent(parent));
         }
@@ -635,67 +1856,65 @@ fn gizmo_instantiate(
             todo!()
         }
         GizmoShape::Sphere { radius } => {
-            commands
+            let entity = commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     transform: Transform::from_scale(Vec3::splat(radius)),
-                    mesh: gizmos.mesh_sphere.clone(),
+                    mesh: gizmos.mesh_sphere[DEFAULT_LOD].clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_sphere.clone()))
+                .id();
+
+            if gizmos.wireframe {
+                commands.entity(entity).insert(Silhouette::Sphere);
+            }
         }
         GizmoShape::Hemisphere { radius } => {
             commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     transform: Transform::from_scale(Vec3::splat(radius)),
-                    mesh: gizmos.mesh_hemisphere.clone(),
+                    mesh: gizmos.mesh_hemisphere[DEFAULT_LOD].clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_hemisphere.clone()));
         }
         GizmoShape::Cylinder { radius, height } => {
             commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     transform: Transform::from_scale(Vec3::new(radius, height, radius)),
-                    mesh: gizmos.mesh_cylinder.clone(),
+                    mesh: gizmos.mesh_cylinder[DEFAULT_LOD].clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_cylinder.clone()));
+        }
+        GizmoShape::Cone { radius, height } => {
+            commands
+                .spawn().insert_bundle(GizmoMeshBundle {
+                    transform: Transform::from_scale(Vec3::new(radius, height, radius)),
+                    mesh: gizmos.mesh_cone[DEFAULT_LOD].clone(),
+                    material,
+                    ..bundle()
+                })
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_cone.clone()));
         }
         GizmoShape::Capsule {
             radius,
             height,
             axis,
         } => {
-            let mut top = Vec3::ZERO;
-            let mut bottom = Vec3::ZERO;
-
-            let offset = height * 0.5;
-            let (rotation, rotation_mirrored) = match axis {
-                Axis::X => {
-                    top[0] = -offset;
-                    bottom[0] = offset;
-                    (
-                        Quat::from_rotation_z(PI * 0.5),
-                        Quat::from_rotation_ypr(PI, 0.0, PI * 0.5),
-                    )
-                }
-                Axis::Y => {
-                    top[1] = offset;
-                    bottom[1] = -offset;
-                    (Quat::default(), Quat::from_rotation_x(PI))
-                }
-                Axis::Z => {
-                    top[2] = offset;
-                    bottom[2] = -offset;
-                    (
-                        Quat::from_rotation_x(PI * 0.5),
-                        Quat::from_rotation_ypr(0.0, PI * 0.5, PI),
-                    )
-                }
-            };
+            let direction = axis.direction();
+            let top = direction * (height * 0.5);
+            let bottom = -top;
+
+            let rotation = rotation_between(Vec3::Y, direction);
+            let rotation_mirrored = rotation * Quat::from_rotation_x(PI);
 
             commands
                 .spawn().insert_bundle(GizmoMeshBundle {
@@ -704,23 +1923,30 @@ fn gizmo_instantiate(
                         rotation,
                         scale: Vec3::splat(radius),
                     },
-                    mesh: gizmos.mesh_capsule_cap.clone(),
+                    mesh: gizmos.mesh_capsule_cap[DEFAULT_LOD].clone(),
                     material: material.clone(),
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
-            commands
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_capsule_cap.clone()));
+            let entity = commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     transform: Transform {
                         translation: Vec3::ZERO,
                         rotation,
                         scale: Vec3::new(radius, height, radius),
                     },
-                    mesh: gizmos.mesh_capsule_body.clone(),
+                    mesh: gizmos.mesh_capsule_body[DEFAULT_LOD].clone(),
                     material: material.clone(),
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_capsule_body.clone()))
+                .id();
+
+            if gizmos.wireframe {
+                commands.entity(entity).insert(Silhouette::Capsule);
+            }
             commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     transform: Transform {
@@ -728,11 +1954,12 @@ fn gizmo_instantiate(
                         rotation: rotation_mirrored,
                         scale: Vec3::splat(radius),
                     },
-                    mesh: gizmos.mesh_capsule_cap.clone(),
+                    mesh: gizmos.mesh_capsule_cap[DEFAULT_LOD].clone(),
                     material,
-                    ..Default::default()
+                    ..bundle()
                 })
-                .insert(Parent(parent));
+                .insert(Parent(parent))
+                .insert(GizmoLod(gizmos.mesh_capsule_cap.clone()));
         }
         GizmoShape::Mesh { mesh } => {
             // Wireframe component ?!?
@@ -751,6 +1978,7 @@ pub enum GizmoStage {
     Update,
 }
 
+/// Draws the gizmos for every camera with a [`GizmoCamera`]
 #[derive(Default)]
 pub struct GizmosPlugin;
 
@@ -762,15 +1990,45 @@ impl Plugin for GizmosPlugin {
             SystemStage::parallel(),
         );
 
-        app.register_type::<GizmoMaterial>().add_system_to_stage(
-            GizmoStage::Update,
-            shader::shader_defs_system::<GizmoMaterial>.system(),
-        );
-
+        // Resources used by the public API, so any code that draws gizmos keeps working
         app.insert_resource(Gizmos::default())
+            .init_resource::<GizmoConfig>()
+            .init_resource::<GizmoTime>()
+            .init_resource::<GizmoCategories>()
+            .init_resource::<GizmoQuality>()
+            .init_resource::<GizmoSilhouettes>();
+
+        // ? NOTE: With the `disabled` feature nothing gets drawn, so there's no need
+        // ? for any mesh, pipeline or system
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.register_type::<GizmoMaterial>()
             .insert_resource(GizmosResources::default())
             .add_startup_system(gizmos_setup.system())
             .add_startup_system(render_graph::gizmos_pipeline_setup.system())
-            .add_system_to_stage(GizmoStage::Update, gizmos_update_system.system());
+            .add_system_to_stage(CoreStage::PreUpdate, gizmos_config_system.system())
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                category::gizmos_categories_system.system(),
+            )
+            // Nothing runs while disabled
+            .add_system_set_to_stage(
+                GizmoStage::Update,
+                SystemSet::new()
+                    .with_run_criteria(gizmos_enabled_criteria.system())
+                    .with_system(shader::shader_defs_system::<GizmoMaterial>.system())
+                    .with_system(render_graph::gizmos_render_graph_system.system())
+                    .with_system(camera::gizmo_camera_layers_system.system())
+                    .with_system(gizmos_update_system.system())
+                    .with_system(gizmos_visibility_system.system())
+                    .with_system(lod::gizmos_lod_system.system()),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(gizmos_enabled_criteria.system())
+                    .with_system(silhouette::gizmos_silhouette_system.system()),
+            );
     }
 }
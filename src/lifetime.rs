@@ -0,0 +1,153 @@
+use bevy::{prelude::*, utils::Instant};
+
+use crate::Gizmos;
+
+/// How long a gizmo lasts, every draw call takes anything that converts into a lifetime,
+/// plain `f32` durations are measured in [`GizmoLifetime::Seconds`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GizmoLifetime {
+    /// Number of frames, `Frames(1)` is the same as an immediate gizmo
+    Frames(u32),
+    /// Seconds measured by the clock selected in [`GizmoTime`]
+    Seconds(f32),
+    /// Number of fixed timestep ticks counted by [`gizmos_fixed_tick_system`], use it for gizmos
+    /// drawn by fixed timestep systems, so they won't flicker or double up
+    FixedTicks(u32),
+    /// Lasts until [`Gizmos::clear`] is called with the same id
+    UntilCleared(u32),
+}
+
+impl Default for GizmoLifetime {
+    fn default() -> Self {
+        GizmoLifetime::Seconds(0.0)
+    }
+}
+
+impl From<f32> for GizmoLifetime {
+    #[inline]
+    fn from(seconds: f32) -> Self {
+        GizmoLifetime::Seconds(seconds)
+    }
+}
+
+impl GizmoLifetime {
+    /// True when the gizmo only lasts for the current frame
+    #[inline]
+    pub fn is_immediate(&self) -> bool {
+        match *self {
+            GizmoLifetime::Frames(frames) => frames <= 1,
+            GizmoLifetime::Seconds(seconds) => seconds <= f32::EPSILON,
+            GizmoLifetime::FixedTicks(_) | GizmoLifetime::UntilCleared(_) => false,
+        }
+    }
+}
+
+/// Clock that drives the expiry of timed gizmos
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GizmoClock {
+    /// Wall clock measured between updates with an [`Instant`], independent of [`Time`],
+    /// ignores both [`GizmoTime::scale`] and [`GizmoTime::paused`]
+    Real,
+    /// Real time multiplied by [`GizmoTime::scale`]
+    Virtual,
+    /// Same as [`GizmoClock::Virtual`] but stops while [`GizmoTime::paused`],
+    /// frame lifetimes are also frozen
+    PausedAware,
+}
+
+/// Controls how the gizmos lifetimes are measured
+pub struct GizmoTime {
+    pub clock: GizmoClock,
+    /// Time scale used by the virtual clocks
+    pub scale: f32,
+    /// Freezes every timed gizmo when using [`GizmoClock::PausedAware`]
+    pub paused: bool,
+}
+
+impl Default for GizmoTime {
+    fn default() -> Self {
+        Self {
+            clock: GizmoClock::PausedAware,
+            scale: 1.0,
+            paused: false,
+        }
+    }
+}
+
+impl GizmoTime {
+    #[inline]
+    fn is_paused(&self) -> bool {
+        self.clock == GizmoClock::PausedAware && self.paused
+    }
+
+    /// Seconds elapsed since the last update, `real` is measured by the wall clock
+    fn delta_seconds(&self, time: &Time, real: f32) -> f32 {
+        match self.clock {
+            GizmoClock::Real => real,
+            GizmoClock::Virtual => time.delta_seconds() * self.scale,
+            GizmoClock::PausedAware if self.paused => 0.0,
+            GizmoClock::PausedAware => time.delta_seconds() * self.scale,
+        }
+    }
+}
+
+/// Counts the fixed timestep ticks used by [`GizmoLifetime::FixedTicks`], add it to your
+/// fixed timestep stage and make it run before any system that draws gizmos
+pub fn gizmos_fixed_tick_system(mut gizmos: ResMut<Gizmos>) {
+    gizmos.fixed_tick += 1;
+}
+
+/// When a timed gizmo will be removed
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Expiry {
+    Frame(u64),
+    Seconds(f64),
+    FixedTick(u64),
+    Cleared(u32),
+}
+
+/// Current time of each clock, advanced once per update
+#[derive(Default)]
+pub(crate) struct GizmoClocks {
+    frame: u64,
+    seconds: f64,
+    fixed_tick: u64,
+    /// Wall clock time of the last update
+    instant: Option<Instant>,
+}
+
+impl GizmoClocks {
+    pub fn advance(&mut self, settings: &GizmoTime, time: &Time, fixed_tick: u64) {
+        if !settings.is_paused() {
+            self.frame += 1;
+        }
+
+        let now = Instant::now();
+        let real = self
+            .instant
+            .map_or(0.0, |instant| (now - instant).as_secs_f32());
+        self.instant = Some(now);
+        self.seconds += settings.delta_seconds(time, real) as f64;
+        self.fixed_tick = fixed_tick;
+    }
+
+    /// Expiry of a gizmo drawn now, `fixed_tick` is the tick count when it was drawn
+    pub fn expiry(&self, lifetime: GizmoLifetime, fixed_tick: u64) -> Expiry {
+        match lifetime {
+            GizmoLifetime::Frames(frames) => Expiry::Frame(self.frame + frames as u64),
+            GizmoLifetime::Seconds(seconds) => Expiry::Seconds(self.seconds + seconds as f64),
+            GizmoLifetime::FixedTicks(ticks) => Expiry::FixedTick(fixed_tick + ticks as u64),
+            GizmoLifetime::UntilCleared(id) => Expiry::Cleared(id),
+        }
+    }
+
+    #[inline]
+    pub fn is_expired(&self, expiry: &Expiry) -> bool {
+        match *expiry {
+            Expiry::Frame(frame) => self.frame >= frame,
+            Expiry::Seconds(seconds) => self.seconds > seconds,
+            Expiry::FixedTick(tick) => self.fixed_tick >= tick,
+            Expiry::Cleared(_) => false,
+        }
+    }
+}
@@ -1,9 +1,109 @@
 use bevy::{
     prelude::*,
-    render::{mesh::Indices, pipeline::PrimitiveTopology},
+    render::{camera::RenderLayers, mesh::Indices, pipeline::PrimitiveTopology},
 };
 
-use crate::{mesh_helper::MeshEditXC, GizmoMeshBundle};
+use crate::{mesh_helper::MeshEditXC, GizmoDepth, GizmoMaterial, GizmoMeshBundle};
+
+/// Screen space offset in pixels applied after the vertex projection, used by the markers
+pub const ATTRIBUTE_OFFSET: &str = "Vertex_Offset";
+
+/// Lines with the same key can share the same mesh
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct LineKey {
+    pub depth: GizmoDepth,
+    pub layers: RenderLayers,
+    /// Points are in pixels, see [`crate::GizmosContext::screen`]
+    pub screen: bool,
+}
+
+/// How the points of a line are connected
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineTopology {
+    /// Each consecutive pair of points
+    Strip,
+    /// Like a strip, but also connects the last point with the first one
+    Loop,
+    /// Disjoint lines, made by each pair of points
+    Segments,
+}
+
+impl LineTopology {
+    /// Pushes the indices of `count` points starting at the vertex `start`
+    pub(crate) fn push_indices(&self, indices: &mut Vec<u32>, start: u32, count: u32) {
+        match self {
+            LineTopology::Strip | LineTopology::Loop => {
+                for i in 1..count {
+                    indices.push(start + i - 1);
+                    indices.push(start + i);
+                }
+                // Two points loop is just a single line
+                if *self == LineTopology::Loop && count > 2 {
+                    indices.push(start + count - 1);
+                    indices.push(start);
+                }
+            }
+            LineTopology::Segments => {
+                // Ignores the last point if unpaired
+                indices.extend(start..(start + count - count % 2));
+            }
+        }
+    }
+}
+
+/// Borrowed line buffers, either from a mesh or from an arena
+pub(crate) struct LineEdit<'a> {
+    pub vertices: &'a mut Vec<[f32; 3]>,
+    pub colors: &'a mut Vec<[f32; 4]>,
+    pub offsets: &'a mut Vec<[f32; 2]>,
+    pub indices: &'a mut Vec<u32>,
+}
+
+impl<'a> LineEdit<'a> {
+    /// Connects the `count` vertices starting at `start`, lines wider than a pixel
+    /// are faked by copying the vertices and moving each copy around by a few pixels
+    pub fn connect(&mut self, start: u32, count: u32, topology: LineTopology, width: f32) {
+        let mut kernel = width_kernel(width);
+
+        // Reuse the original vertices for the first offset
+        let first = kernel.next().unwrap_or([0.0; 2]);
+        let range = start as usize..(start + count) as usize;
+        for offset in &mut self.offsets[range.clone()] {
+            offset[0] += first[0];
+            offset[1] += first[1];
+        }
+        topology.push_indices(self.indices, start, count);
+
+        for k in kernel {
+            let copy = self.vertices.len() as u32;
+            for j in range.clone() {
+                let offset = self.offsets[j];
+                self.vertices.push(self.vertices[j]);
+                self.colors.push(self.colors[j]);
+                self.offsets
+                    .push([offset[0] - first[0] + k[0], offset[1] - first[1] + k[1]]);
+            }
+            topology.push_indices(self.indices, copy, count);
+        }
+    }
+}
+
+/// Screen space offsets (in pixels) used to fake thick lines, each line is drawn once per offset
+fn width_kernel(width: f32) -> impl Iterator<Item = [f32; 2]> {
+    let n = width.round().max(1.0) as i32;
+    let half = (n - 1) as f32 * 0.5;
+    // Round the corners for a less boxy look
+    let radius_squared = half * half + 0.5;
+    (0..n * n).filter_map(move |k| {
+        let x = (k % n) as f32 - half;
+        let y = (k / n) as f32 - half;
+        if x * x + y * y <= radius_squared {
+            Some([x, y])
+        } else {
+            None
+        }
+    })
+}
 
 #[derive(Default)]
 pub struct Line {
@@ -12,11 +112,12 @@ pub struct Line {
 }
 
 impl Line {
-    pub fn new(commands: &mut Commands, meshes: &mut Assets<Mesh>) -> Self {
+    pub fn new(commands: &mut Commands, meshes: &mut Assets<Mesh>, key: LineKey) -> Self {
         let mesh_handle = {
             let mut mesh = Mesh::new(PrimitiveTopology::LineList);
             mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::with_capacity(32));
             mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, Vec::<[f32; 4]>::with_capacity(32));
+            mesh.set_attribute(ATTRIBUTE_OFFSET, Vec::<[f32; 2]>::with_capacity(32));
             mesh.set_indices(Some(Indices::U32(Vec::with_capacity(32))));
             meshes.add(mesh)
         };
@@ -25,14 +126,24 @@ impl Line {
             entity: Some(commands
                 .spawn().insert_bundle(GizmoMeshBundle {
                     mesh: mesh_handle.clone(),
-                    material: Color::WHITE.into(),
-                    ..Default::default()
+                    material: GizmoMaterial {
+                        screen_offset: true,
+                        screen_space: key.screen,
+                        ..Default::default()
+                    },
+                    render_layers: key.layers,
+                    ..GizmoMeshBundle::with_depth(key.depth)
                 })
                 .id()),
             mesh_handle,
         }
     }
 
+    #[inline]
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+
     pub fn edit<'a>(&self, meshes: &'a mut Assets<Mesh>) -> MeshEditXC<'a> {
         let mesh = meshes.get_mut(&self.mesh_handle).unwrap();
         MeshEditXC::from(mesh)
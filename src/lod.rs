@@ -0,0 +1,90 @@
+use bevy::{
+    prelude::*,
+    render::camera::{Camera, RenderLayers},
+};
+
+use crate::{camera::main_camera, gen::LOD_COUNT, Gizmos};
+
+pub(crate) type LodMeshes = [Handle<Mesh>; LOD_COUNT];
+
+/// Level used when spawning a new curved gizmo, before the first lod selection
+pub(crate) const DEFAULT_LOD: usize = 1;
+
+/// Global settings that controls the tessellation of curved gizmos
+pub struct GizmoQuality {
+    /// Multiplies the projected screen size (in pixels) before the lod selection,
+    /// higher values will pick higher quality meshes
+    pub scale: f32,
+    /// Minimum screen size (in pixels) required by each lod level, except the last one
+    pub thresholds: [f32; LOD_COUNT - 1],
+    /// Always use this lod level, where `0` is the highest quality
+    pub force_lod: Option<usize>,
+}
+
+impl Default for GizmoQuality {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            thresholds: [256.0, 48.0],
+            force_lod: None,
+        }
+    }
+}
+
+impl GizmoQuality {
+    fn select(&self, screen_size: f32) -> usize {
+        if let Some(lod) = self.force_lod {
+            return lod.min(LOD_COUNT - 1);
+        }
+
+        let screen_size = screen_size * self.scale;
+        self.thresholds
+            .iter()
+            .position(|threshold| screen_size > *threshold)
+            .unwrap_or(LOD_COUNT - 1)
+    }
+}
+
+/// Tracks the lod meshes of a curved gizmo mesh
+pub(crate) struct GizmoLod(pub LodMeshes);
+
+pub(crate) fn gizmos_lod_system(
+    quality: Res<GizmoQuality>,
+    gizmos: Res<Gizmos>,
+    windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    mut query: Query<(&GizmoLod, &GlobalTransform, &mut Handle<Mesh>)>,
+) {
+    if !gizmos.is_enabled() {
+        return;
+    }
+
+    let (camera, camera_transform, _) = if let Some(camera) = main_camera(&cameras) {
+        camera
+    } else {
+        return;
+    };
+
+    let height = if let Some(window) = windows.get(camera.window) {
+        window.physical_height() as f32
+    } else {
+        return;
+    };
+
+    let view_proj = camera.projection_matrix * camera_transform.compute_matrix().inverse();
+    // Scale factor of the projection in the y axis, works for both perspective and orthographic
+    let focal = camera.projection_matrix.y_axis.y;
+
+    for (lod, global_transform, mut mesh) in query.iter_mut() {
+        // Meshes are unit sized so the radius is given by the scale
+        let radius = global_transform.scale.x.max(global_transform.scale.z);
+        let clip = view_proj * global_transform.translation.extend(1.0);
+        let w = clip.w.abs().max(f32::EPSILON);
+        let screen_size = radius * focal / w * height;
+
+        let level = quality.select(screen_size);
+        if *mesh != lod.0[level] {
+            *mesh = lod.0[level].clone();
+        }
+    }
+}
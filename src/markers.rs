@@ -0,0 +1,139 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::line::{LineEdit, LineTopology};
+
+const CIRCLE_SEGMENTS: usize = 12;
+
+/// Outline drawn by a marker, every marker keeps the same size on screen regardless of the distance
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MarkerStyle {
+    /// Diagonal cross, like a `x`
+    Cross,
+    /// Like a `+`
+    Plus,
+    Square,
+    Diamond,
+    Circle,
+    /// Five pointed star
+    Star,
+}
+
+/// Appends the marker lines to the mesh, each vertex is placed at `position`
+/// and then moved in screen space by his offset (in pixels)
+pub(crate) fn append_marker(
+    edit: &mut LineEdit,
+    position: Vec3,
+    style: MarkerStyle,
+    size: f32,
+    width: f32,
+    color: Color,
+) {
+    let h = size * 0.5;
+    match style {
+        MarkerStyle::Cross => append_segments(
+            edit,
+            position,
+            width,
+            color,
+            &[[-h, -h], [h, h], [-h, h], [h, -h]],
+        ),
+        MarkerStyle::Plus => append_segments(
+            edit,
+            position,
+            width,
+            color,
+            &[[-h, 0.0], [h, 0.0], [0.0, -h], [0.0, h]],
+        ),
+        MarkerStyle::Square => append_loop(
+            edit,
+            position,
+            width,
+            color,
+            [[-h, -h], [h, -h], [h, h], [-h, h]].iter().copied(),
+        ),
+        MarkerStyle::Diamond => append_loop(
+            edit,
+            position,
+            width,
+            color,
+            [[0.0, -h], [h, 0.0], [0.0, h], [-h, 0.0]].iter().copied(),
+        ),
+        MarkerStyle::Circle => append_loop(
+            edit,
+            position,
+            width,
+            color,
+            (0..CIRCLE_SEGMENTS).map(|i| {
+                let t = (i as f32) * (2.0 / CIRCLE_SEGMENTS as f32);
+                let (y, x) = f32::sin_cos(t * PI);
+                [x * h, y * h]
+            }),
+        ),
+        MarkerStyle::Star => append_loop(
+            edit,
+            position,
+            width,
+            color,
+            (0..10).map(|i| {
+                // Alternate between the outer and inner points, starting from the top
+                let r = if i % 2 == 0 { h } else { h * 0.4 };
+                let t = (i as f32) * (2.0 / 10.0) + 0.5;
+                let (y, x) = f32::sin_cos(t * PI);
+                [x * r, y * r]
+            }),
+        ),
+    }
+}
+
+/// Pushes a vertex at `position` for each offset
+fn append_offsets(
+    edit: &mut LineEdit,
+    position: Vec3,
+    width: f32,
+    color: Color,
+    offsets: impl Iterator<Item = [f32; 2]>,
+    topology: LineTopology,
+) {
+    let v = edit.vertices.len() as u32;
+    for offset in offsets {
+        edit.vertices.push(position.into());
+        edit.colors.push(color.into());
+        edit.offsets.push(offset);
+    }
+
+    let count = edit.vertices.len() as u32 - v;
+    edit.connect(v, count, topology, width);
+}
+
+/// Each pair of offsets makes a line
+#[inline]
+fn append_segments(
+    edit: &mut LineEdit,
+    position: Vec3,
+    width: f32,
+    color: Color,
+    offsets: &[[f32; 2]],
+) {
+    append_offsets(
+        edit,
+        position,
+        width,
+        color,
+        offsets.iter().copied(),
+        LineTopology::Segments,
+    );
+}
+
+/// Closed loop of lines
+#[inline]
+fn append_loop(
+    edit: &mut LineEdit,
+    position: Vec3,
+    width: f32,
+    color: Color,
+    offsets: impl Iterator<Item = [f32; 2]>,
+) {
+    append_offsets(edit, position, width, color, offsets, LineTopology::Loop);
+}
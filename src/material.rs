@@ -20,6 +20,23 @@ pub struct GizmoMaterial {
     #[render_resources(ignore)]
     pub billboard: bool,
     pub billboard_size: f32,
+
+    /// Moves each vertex by his `Vertex_Offset` (in pixels) after the projection
+    #[shader_def]
+    #[render_resources(ignore)]
+    pub screen_offset: bool,
+
+    /// Vertices are in pixels from the bottom left corner of the screen, the camera is ignored
+    #[shader_def]
+    #[render_resources(ignore)]
+    pub screen_space: bool,
+
+    /// Applies the `deform_matrix` in world space on top of the model matrix, used by shapes
+    /// with shear or non-uniform scales that a [`Transform`] can't hold
+    #[shader_def]
+    #[render_resources(ignore)]
+    pub deform: bool,
+    pub deform_matrix: Mat4,
 }
 
 impl Default for GizmoMaterial {
@@ -31,6 +48,10 @@ impl Default for GizmoMaterial {
             texture: None,
             billboard: false,
             billboard_size: 0.5,
+            screen_offset: false,
+            screen_space: false,
+            deform: false,
+            deform_matrix: Mat4::IDENTITY,
         }
     }
 }
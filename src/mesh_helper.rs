@@ -3,6 +3,8 @@ use bevy::{
     render::mesh::{Indices, VertexAttributeValues},
 };
 
+use crate::line::{LineEdit, ATTRIBUTE_OFFSET};
+
 macro_rules! mesh_attr {
     ($mesh:tt , $attr:expr, $var:path) => {
         if let Some($var(buffer)) = $mesh.attribute_mut($attr) {
@@ -42,6 +44,8 @@ macro_rules! mesh_attr {
 pub struct MeshEditXC<'a> {
     pub vertices: &'a mut Vec<[f32; 3]>,
     pub colors: &'a mut Vec<[f32; 4]>,
+    /// Screen space offset (in pixels), see [`crate::line::ATTRIBUTE_OFFSET`]
+    pub offsets: &'a mut Vec<[f32; 2]>,
     pub indices: &'a mut Vec<u32>,
     mesh: &'a mut Mesh,
 }
@@ -56,6 +60,7 @@ impl<'a> From<&'a mut Mesh> for MeshEditXC<'a> {
                     VertexAttributeValues::Float3
                 ),
                 colors: mesh_attr!(mesh, Mesh::ATTRIBUTE_COLOR, VertexAttributeValues::Float4),
+                offsets: mesh_attr!(mesh, ATTRIBUTE_OFFSET, VertexAttributeValues::Float2),
                 indices: if let Some(Indices::U32(buffer)) = mesh.indices_mut() {
                     &mut *(buffer as *mut _)
                 } else {
@@ -67,6 +72,18 @@ impl<'a> From<&'a mut Mesh> for MeshEditXC<'a> {
     }
 }
 
+impl<'a> MeshEditXC<'a> {
+    #[inline]
+    pub(crate) fn lines(&mut self) -> LineEdit {
+        LineEdit {
+            vertices: self.vertices,
+            colors: self.colors,
+            offsets: self.offsets,
+            indices: self.indices,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // TODO: Test for fail compilation
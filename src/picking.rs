@@ -0,0 +1,461 @@
+use bevy::{
+    prelude::*,
+    render::camera::{Camera, RenderLayers},
+};
+
+use crate::{camera, rotation_between, Gizmo, GizmoShape, Gizmos};
+
+/// Cursor picking settings used by the [`GizmoPickingPlugin`]
+pub struct GizmoPicking {
+    /// Camera used to cast the cursor ray, the main camera is used when `None`
+    pub camera: Option<Entity>,
+    pub button: MouseButton,
+    /// Wireframe color of the hovered gizmo, the original color is restored once the gizmo isn't hovered
+    ///
+    /// **NOTE** Changing the [`Gizmo`] wireframe while hovered is lost when the highlight ends
+    pub highlight: Option<Color>,
+    /// Max distance from the cursor ray to pick line only gizmos, like [`GizmoShape::Empty`]
+    pub line_tolerance: f32,
+    /// Gizmo under the cursor, updated by the [`GizmoPickingPlugin`]
+    pub hovered: Option<Entity>,
+}
+
+impl Default for GizmoPicking {
+    fn default() -> Self {
+        Self {
+            camera: None,
+            button: MouseButton::Left,
+            highlight: None,
+            line_tolerance: 0.05,
+            hovered: None,
+        }
+    }
+}
+
+/// Sent when the cursor starts hovering a gizmo
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoHovered {
+    pub entity: Entity,
+    /// Hit position in world space
+    pub point: Vec3,
+}
+
+/// Sent when the [`GizmoPicking::button`] is pressed over a gizmo
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoClicked {
+    pub entity: Entity,
+    /// Hit position in world space
+    pub point: Vec3,
+}
+
+/// Picks persistent [`Gizmo`]s under the cursor, see [`GizmoPicking`]
+#[derive(Default)]
+pub struct GizmoPickingPlugin;
+
+impl Plugin for GizmoPickingPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<GizmoPicking>()
+            .add_event::<GizmoHovered>()
+            .add_event::<GizmoClicked>();
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.add_system(gizmos_picking_system.system());
+    }
+}
+
+fn gizmos_picking_system(
+    gizmos: Res<Gizmos>,
+    windows: Res<Windows>,
+    mouse: Res<Input<MouseButton>>,
+    mut picking: ResMut<GizmoPicking>,
+    mut highlighted: Local<Option<(Entity, Color)>>,
+    mut hovered_events: EventWriter<GizmoHovered>,
+    mut clicked_events: EventWriter<GizmoClicked>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    mut gizmos_query: QuerySet<(Query<(Entity, &Gizmo, &GlobalTransform)>, Query<&mut Gizmo>)>,
+) {
+    // Cursor ray and the layers seen by the camera
+    let view = if gizmos.is_enabled() {
+        picking
+            .camera
+            .and_then(|entity| cameras.get(entity).ok())
+            .map(|(camera, transform, layers)| (camera, transform, camera::camera_layers(layers)))
+            .or_else(|| camera::main_camera(&cameras))
+            .and_then(|(camera, transform, layers)| {
+                cursor_ray(camera, transform, &windows).map(|ray| (ray, layers))
+            })
+    } else {
+        None
+    };
+    let ray = view.map(|(ray, _)| ray);
+
+    // Closest hit
+    let mut hit: Option<(Entity, f32)> = None;
+    if let Some(((origin, direction), layers)) = view {
+        for (entity, gizmo, transform) in gizmos_query.q0().iter() {
+            // Hidden or not seen by the camera
+            if (gizmo.mask & gizmos.mask) == 0 || !layers.intersects(&gizmo.layers) {
+                continue;
+            }
+
+            let t = ray_gizmo(
+                origin,
+                direction,
+                &gizmo.shape,
+                transform,
+                picking.line_tolerance,
+            );
+            if let Some(t) = t {
+                if hit.map_or(true, |(_, closest)| t < closest) {
+                    hit = Some((entity, t));
+                }
+            }
+        }
+    }
+
+    let entity = hit.map(|(entity, _)| entity);
+    if entity != picking.hovered {
+        picking.hovered = entity;
+
+        // Restore the previous highlighted gizmo
+        if let Some((entity, wireframe)) = highlighted.take() {
+            if let Ok(mut gizmo) = gizmos_query.q1_mut().get_mut(entity) {
+                gizmo.wireframe = wireframe;
+            }
+        }
+
+        if let (Some((entity, t)), Some((origin, direction))) = (hit, ray) {
+            hovered_events.send(GizmoHovered {
+                entity,
+                point: origin + direction * t,
+            });
+
+            if let Some(color) = picking.highlight {
+                if let Ok(mut gizmo) = gizmos_query.q1_mut().get_mut(entity) {
+                    *highlighted = Some((entity, gizmo.wireframe));
+                    gizmo.wireframe = color;
+                }
+            }
+        }
+    }
+
+    if mouse.just_pressed(picking.button) {
+        if let (Some((entity, t)), Some((origin, direction))) = (hit, ray) {
+            clicked_events.send(GizmoClicked {
+                entity,
+                point: origin + direction * t,
+            });
+        }
+    }
+}
+
+/// World space ray under the cursor, the direction is normalized
+fn cursor_ray(
+    camera: &Camera,
+    transform: &GlobalTransform,
+    windows: &Windows,
+) -> Option<(Vec3, Vec3)> {
+    let window = windows.get(camera.window)?;
+    let cursor = window.cursor_position()?;
+    let ndc = cursor / Vec2::new(window.width(), window.height()) * 2.0 - Vec2::ONE;
+
+    let inverse = (camera.projection_matrix * transform.compute_matrix().inverse()).inverse();
+    let unproject = |z: f32| {
+        let p = inverse * Vec4::new(ndc.x, ndc.y, z, 1.0);
+        p.truncate() / p.w
+    };
+
+    // From the near to the far plane
+    let near = unproject(0.0);
+    let direction = (unproject(1.0) - near).normalize();
+    Some((near, direction))
+}
+
+/// Distance along the ray to the gizmo `shape`, `None` if missed
+fn ray_gizmo(
+    origin: Vec3,
+    direction: Vec3,
+    shape: &GizmoShape,
+    transform: &GlobalTransform,
+    line_tolerance: f32,
+) -> Option<f32> {
+    let matrix = transform.compute_matrix();
+
+    // Ray in the gizmo space, the direction isn't normalized
+    // to keep the distances along the ray the same
+    let inverse = matrix.inverse();
+    let o = inverse.transform_point3(origin);
+    let d = inverse.transform_vector3(direction);
+
+    match shape {
+        GizmoShape::Empty { radius } => {
+            let h = radius * 0.5;
+            [Vec3::X, Vec3::Y, Vec3::Z]
+                .iter()
+                .filter_map(|axis| {
+                    let a = matrix.transform_point3(*axis * h);
+                    let b = matrix.transform_point3(*axis * -h);
+                    ray_segment(origin, direction, a, b, line_tolerance)
+                })
+                .fold(None, |closest: Option<f32>, t| {
+                    Some(closest.map_or(t, |closest| closest.min(t)))
+                })
+        }
+        // Billboards are sized in screen space, so this is just an approximation
+        GizmoShape::Billboard { size, .. } => {
+            ray_sphere(o, d, Vec3::ZERO, size * 0.5).and_then(first_hit)
+        }
+        GizmoShape::Cube { size } => ray_box(o, d, *size * 0.5),
+        GizmoShape::Sphere { radius } => ray_sphere(o, d, Vec3::ZERO, *radius).and_then(first_hit),
+        GizmoShape::Hemisphere { radius } => {
+            let (t0, t1) = ray_sphere(o, d, Vec3::ZERO, *radius)?;
+            // Only the upper half
+            [t0, t1]
+                .iter()
+                .copied()
+                .find(|t| *t >= 0.0 && (o + d * *t).y >= 0.0)
+        }
+        GizmoShape::Cylinder { radius, height } => ray_cylinder(o, d, *radius, height * 0.5, true),
+        // Tested as the cylinder that bounds the cone
+        GizmoShape::Cone { radius, height } => ray_cylinder(o, d, *radius, height * 0.5, true),
+        GizmoShape::Capsule {
+            radius,
+            height,
+            axis,
+        } => {
+            // Capsule along the Y axis
+            let rotation = rotation_between(Vec3::Y, axis.direction()).inverse();
+            let (o, d) = (rotation * o, rotation * d);
+            let h = height * 0.5;
+
+            [
+                ray_cylinder(o, d, *radius, h, false),
+                ray_sphere(o, d, Vec3::Y * h, *radius).and_then(first_hit),
+                ray_sphere(o, d, Vec3::Y * -h, *radius).and_then(first_hit),
+            ]
+            .iter()
+            .flatten()
+            .copied()
+            .fold(None, |closest: Option<f32>, t| {
+                Some(closest.map_or(t, |closest| closest.min(t)))
+            })
+        }
+        // TODO: Circle and Mesh gizmos aren't implemented yet
+        GizmoShape::Circle { .. } | GizmoShape::Mesh { .. } => None,
+    }
+}
+
+/// First of the two hits in front of the ray
+#[inline]
+fn first_hit((t0, t1): (f32, f32)) -> Option<f32> {
+    if t0 >= 0.0 {
+        Some(t0)
+    } else if t1 >= 0.0 {
+        Some(t1)
+    } else {
+        None
+    }
+}
+
+/// Both hits with the sphere, sorted
+fn ray_sphere(o: Vec3, d: Vec3, center: Vec3, radius: f32) -> Option<(f32, f32)> {
+    let oc = o - center;
+    let a = d.dot(d);
+    let b = oc.dot(d);
+    let c = oc.dot(oc) - radius * radius;
+
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 || a <= f32::EPSILON {
+        return None;
+    }
+
+    let s = discriminant.sqrt();
+    Some(((-b - s) / a, (-b + s) / a))
+}
+
+/// Box centered at the origin
+fn ray_box(o: Vec3, d: Vec3, half_extents: Vec3) -> Option<f32> {
+    let (o, d, h): ([f32; 3], [f32; 3], [f32; 3]) = (o.into(), d.into(), half_extents.into());
+
+    // Intersection of the 3 slabs
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+    for ((o, d), h) in o.iter().zip(&d).zip(&h) {
+        if d.abs() <= f32::EPSILON {
+            // Parallel to the slab, it must start within it
+            if o.abs() > *h {
+                return None;
+            }
+        } else {
+            let t0 = (-h - o) / d;
+            let t1 = (h - o) / d;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+    }
+
+    if t_max >= t_min {
+        Some(t_min)
+    } else {
+        None
+    }
+}
+
+/// Cylinder along the Y axis centered at the origin, the `caps` can be left out
+fn ray_cylinder(o: Vec3, d: Vec3, radius: f32, half_height: f32, caps: bool) -> Option<f32> {
+    let r2 = radius * radius;
+    let mut closest: Option<f32> = None;
+    let mut hit = |t: f32| {
+        if t >= 0.0 && closest.map_or(true, |closest| t < closest) {
+            closest = Some(t);
+        }
+    };
+
+    // Side
+    let a = d.x * d.x + d.z * d.z;
+    if a > f32::EPSILON {
+        let b = o.x * d.x + o.z * d.z;
+        let c = o.x * o.x + o.z * o.z - r2;
+        let discriminant = b * b - a * c;
+        if discriminant >= 0.0 {
+            let s = discriminant.sqrt();
+            for t in [(-b - s) / a, (-b + s) / a].iter().copied() {
+                if (o.y + d.y * t).abs() <= half_height {
+                    hit(t);
+                }
+            }
+        }
+    }
+
+    // Caps
+    if caps && d.y.abs() > f32::EPSILON {
+        for y in [half_height, -half_height].iter().copied() {
+            let t = (y - o.y) / d.y;
+            let p = o + d * t;
+            if p.x * p.x + p.z * p.z <= r2 {
+                hit(t);
+            }
+        }
+    }
+
+    closest
+}
+
+/// Distance along the ray to the closest point of the segment `a` to `b`,
+/// `None` if the segment is further than `tolerance`; the direction must be normalized
+fn ray_segment(o: Vec3, d: Vec3, a: Vec3, b: Vec3, tolerance: f32) -> Option<f32> {
+    let ab = b - a;
+    let ao = o - a;
+    let ab_ab = ab.dot(ab);
+    let ab_d = ab.dot(d);
+    let denominator = ab_ab - ab_d * ab_d;
+
+    // Closest point of the segment, the ray and the segment may be parallel
+    let s = if denominator > f32::EPSILON {
+        ((ab.dot(ao) - ab_d * d.dot(ao)) / denominator)
+            .max(0.0)
+            .min(1.0)
+    } else {
+        0.0
+    };
+    let p = a + ab * s;
+
+    let t = (p - o).dot(d);
+    if t < 0.0 || (o + d * t).distance_squared(p) > tolerance * tolerance {
+        return None;
+    }
+    Some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_hit(t: Option<f32>, expected: f32) {
+        let t = t.expect("expected a hit");
+        assert!(
+            (t - expected).abs() < 1e-4,
+            "hit at {} instead of {}",
+            t,
+            expected
+        );
+    }
+
+    #[test]
+    fn sphere() {
+        let hit = ray_sphere(Vec3::new(0.0, 0.0, -5.0), Vec3::Z, Vec3::ZERO, 1.0);
+        assert_hit(hit.and_then(first_hit), 4.0);
+
+        let miss = ray_sphere(Vec3::new(0.0, 2.0, -5.0), Vec3::Z, Vec3::ZERO, 1.0);
+        assert!(miss.is_none());
+
+        let behind = ray_sphere(Vec3::new(0.0, 0.0, 5.0), Vec3::Z, Vec3::ZERO, 1.0);
+        assert!(behind.and_then(first_hit).is_none());
+
+        // Only the exit point is in front of the ray
+        let inside = ray_sphere(Vec3::ZERO, Vec3::Z, Vec3::ZERO, 1.0);
+        assert_hit(inside.and_then(first_hit), 1.0);
+    }
+
+    #[test]
+    fn cube() {
+        let half_extents = Vec3::ONE;
+        assert_hit(
+            ray_box(Vec3::new(0.0, 0.0, -5.0), Vec3::Z, half_extents),
+            4.0,
+        );
+        assert!(ray_box(Vec3::new(0.0, 2.0, -5.0), Vec3::Z, half_extents).is_none());
+        assert!(ray_box(Vec3::new(0.0, 0.0, 5.0), Vec3::Z, half_extents).is_none());
+
+        // Hits right away
+        assert_hit(ray_box(Vec3::ZERO, Vec3::Z, half_extents), 0.0);
+
+        // Axis aligned rays, grazing a face and just outside of it
+        assert_hit(
+            ray_box(Vec3::new(1.0, 0.0, -5.0), Vec3::Z, half_extents),
+            4.0,
+        );
+        assert_hit(
+            ray_box(Vec3::new(-5.0, -1.0, 1.0), Vec3::X, half_extents),
+            4.0,
+        );
+        assert!(ray_box(Vec3::new(1.5, 0.0, -5.0), Vec3::Z, half_extents).is_none());
+    }
+
+    #[test]
+    fn cylinder() {
+        // Side
+        assert_hit(
+            ray_cylinder(Vec3::new(-5.0, 0.0, 0.0), Vec3::X, 1.0, 1.0, true),
+            4.0,
+        );
+        assert!(ray_cylinder(Vec3::new(-5.0, 2.0, 0.0), Vec3::X, 1.0, 1.0, true).is_none());
+
+        // Caps
+        assert_hit(
+            ray_cylinder(Vec3::new(0.0, 5.0, 0.0), -Vec3::Y, 1.0, 1.0, true),
+            4.0,
+        );
+        assert!(ray_cylinder(Vec3::new(0.0, 5.0, 0.0), -Vec3::Y, 1.0, 1.0, false).is_none());
+
+        assert_hit(ray_cylinder(Vec3::ZERO, Vec3::X, 1.0, 1.0, true), 1.0);
+    }
+
+    #[test]
+    fn segment() {
+        let (a, b) = (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_hit(
+            ray_segment(Vec3::new(0.0, 0.0, -5.0), Vec3::Z, a, b, 0.1),
+            5.0,
+        );
+
+        let (c, d) = (Vec3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        assert!(ray_segment(Vec3::new(0.0, 0.0, -5.0), Vec3::Z, c, d, 0.1).is_none());
+        assert!(ray_segment(Vec3::new(0.0, 0.0, 5.0), Vec3::Z, a, b, 0.1).is_none());
+
+        // Starts on the segment
+        assert_hit(ray_segment(Vec3::ZERO, Vec3::Z, a, b, 0.1), 0.0);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{line::LineTopology, Gizmos, GizmosContext};
+
+/// Settings of a rolling line graph, see [`GizmosContext::plot`]
+#[derive(Debug, Copy, Clone)]
+pub struct GizmoPlot {
+    /// Number of samples kept in the history
+    pub capacity: usize,
+    /// Size in pixels
+    pub size: Vec2,
+    /// Value range, when `None` it fits the samples in the history
+    pub range: Option<(f32, f32)>,
+    pub axes: Color,
+}
+
+impl Default for GizmoPlot {
+    fn default() -> Self {
+        Self {
+            capacity: 120,
+            size: Vec2::new(160.0, 64.0),
+            range: None,
+            axes: Color::GRAY,
+        }
+    }
+}
+
+/// Samples of each plot, kept between frames
+pub(crate) type PlotHistory = VecDeque<f32>;
+
+impl Gizmos {
+    /// Forgets the history of the plot `name`
+    pub fn clear_plot(&self, name: &str) -> &Self {
+        self.plots.lock().unwrap().remove(name);
+        self
+    }
+}
+
+impl<'a> GizmosContext<'a> {
+    /// Pushes the `value` into the history of the plot `name` and draws it as a line graph,
+    /// newest sample on the right; the graph bottom left corner is placed at the `anchor` and keeps
+    /// the same size in pixels regardless of the distance to the camera, in screen space the `anchor`
+    /// is in pixels, see [`GizmosContext::screen`]
+    ///
+    /// **NOTE** The graph only lasts a single frame, so call it every frame to keep it around
+    pub fn plot(&mut self, name: &str, value: f32, anchor: Vec3, plot: &GizmoPlot) -> &mut Self {
+        let anchor = self.matrix().transform_point3(anchor);
+        let (w, h) = (plot.size.x, plot.size.y);
+        let color = self.style.wireframe;
+        let width = self.style.line_width;
+
+        let command_buffer = self.command_buffer;
+        let mut plots = command_buffer.plots.lock().unwrap();
+        // Only allocates the name once
+        if !plots.contains_key(name) {
+            plots.insert(name.to_string(), PlotHistory::with_capacity(plot.capacity));
+        }
+        let history = plots.get_mut(name).unwrap();
+        history.push_back(value);
+        while history.len() > plot.capacity {
+            history.pop_front();
+        }
+
+        let (min, max) = plot.range.unwrap_or_else(|| {
+            history.iter().fold((f32::MAX, f32::MIN), |(min, max), v| {
+                (min.min(*v), max.max(*v))
+            })
+        });
+        // Flat lines stay in the middle
+        let (min, max) = if max - min > f32::EPSILON {
+            (min, max)
+        } else {
+            (min - 0.5, max + 0.5)
+        };
+        let y = |v: f32| (v.max(min).min(max) - min) / (max - min) * h;
+
+        let arena = self.arena();
+
+        // Axes, also the zero line when in range
+        arena.offset_lines(
+            anchor,
+            [[0.0, h], [0.0, 0.0], [w, 0.0]]
+                .iter()
+                .map(|offset| (*offset, plot.axes)),
+            LineTopology::Strip,
+            1.0,
+        );
+        if min < 0.0 && max > 0.0 {
+            arena.offset_lines(
+                anchor,
+                [[0.0, y(0.0)], [w, y(0.0)]]
+                    .iter()
+                    .map(|offset| (*offset, plot.axes)),
+                LineTopology::Segments,
+                1.0,
+            );
+        }
+
+        // Samples, aligned to the right while the history fills up
+        let step = w / (plot.capacity.max(2) - 1) as f32;
+        let start = plot.capacity.saturating_sub(history.len());
+        arena.offset_lines(
+            anchor,
+            history
+                .iter()
+                .enumerate()
+                .map(|(i, v)| ([(start + i) as f32 * step, y(*v)], color)),
+            LineTopology::Strip,
+            width,
+        );
+
+        self
+    }
+}
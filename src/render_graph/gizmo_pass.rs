@@ -0,0 +1,168 @@
+use bevy::{
+    prelude::*,
+    render::{
+        pass::{
+            LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+            TextureAttachment,
+        },
+        render_graph::{
+            base, Node, PassNode, RenderGraph, ResourceSlotInfo, ResourceSlots,
+            WindowSwapChainNode, WindowTextureNode,
+        },
+        renderer::RenderContext,
+    },
+};
+
+use super::{screen_info_node::SCREEN_INFO_NODE, GIZMO_MATERIAL_NODE};
+use crate::Gizmos;
+
+/// Gizmo pass of the primary window
+pub const GIZMO_PASS: &str = "gizmo_pass";
+
+/// Pass of `bevy_ui`, the gizmos are drawn before it so they stay under the ui
+const UI_PASS: &str = "ui_pass";
+
+/// Entities drawn by the gizmo passes, only cameras with a [`crate::GizmoCamera`] see them
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GizmoPass;
+
+/// Pass that draws the [`GizmoPass`] entities, skipped while the gizmos are disabled
+pub(crate) struct GizmoPassNode {
+    pass: PassNode<&'static GizmoPass>,
+    /// Gizmos state read when preparing the pass, so a frame is either fully drawn or skipped
+    enabled: bool,
+}
+
+impl GizmoPassNode {
+    #[inline]
+    pub fn add_camera(&mut self, camera_name: &str) {
+        self.pass.add_camera(camera_name);
+    }
+}
+
+impl Node for GizmoPassNode {
+    fn input(&self) -> &[ResourceSlotInfo] {
+        self.pass.input()
+    }
+
+    fn prepare(&mut self, world: &mut World) {
+        self.enabled = world
+            .get_resource::<Gizmos>()
+            .map_or(false, Gizmos::is_enabled);
+        if self.enabled {
+            self.pass.prepare(world);
+        }
+    }
+
+    fn update(
+        &mut self,
+        world: &World,
+        render_context: &mut dyn RenderContext,
+        input: &ResourceSlots,
+        output: &mut ResourceSlots,
+    ) {
+        if self.enabled {
+            self.pass.update(world, render_context, input, output);
+        }
+    }
+}
+
+/// Render graph nodes used by a gizmo pass, see [`add_gizmo_pass`]
+#[derive(Debug, Clone, Copy)]
+pub struct GizmoPassTarget {
+    /// Node with the color texture, like the window swap chain
+    pub color: &'static str,
+    /// Node with the depth texture of the scene, so gizmos are still hidden by it
+    pub depth: &'static str,
+    /// Node with the multisampled color texture, required when [`Msaa`] has more than one sample
+    pub sampled_color: Option<&'static str>,
+    /// Pass that draws the scene, the gizmos are drawn on top of it
+    pub after: &'static str,
+}
+
+impl Default for GizmoPassTarget {
+    /// Primary window nodes
+    fn default() -> Self {
+        Self {
+            color: base::node::PRIMARY_SWAP_CHAIN,
+            depth: base::node::MAIN_DEPTH_TEXTURE,
+            sampled_color: Some(base::node::MAIN_SAMPLED_COLOR_ATTACHMENT),
+            after: base::node::MAIN_PASS,
+        }
+    }
+}
+
+/// Adds a gizmo pass called `name`, that draws on top of the `target` scene; the primary window
+/// already has one, see [`GIZMO_PASS`], other windows or render targets need their own pass
+pub fn add_gizmo_pass(
+    render_graph: &mut RenderGraph,
+    msaa: &Msaa,
+    name: &'static str,
+    target: GizmoPassTarget,
+) {
+    // Keeps both the color and depth of the scene
+    let pass = PassNode::new(PassDescriptor {
+        color_attachments: vec![msaa.color_attachment(
+            TextureAttachment::Input("color_attachment".to_string()),
+            TextureAttachment::Input("color_resolve_target".to_string()),
+            Operations {
+                load: LoadOp::Load,
+                store: true,
+            },
+        )],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+            attachment: TextureAttachment::Input("depth".to_string()),
+            depth_ops: Some(Operations {
+                load: LoadOp::Load,
+                store: true,
+            }),
+            stencil_ops: None,
+        }),
+        sample_count: msaa.samples,
+    });
+    render_graph.add_node(
+        name,
+        GizmoPassNode {
+            pass,
+            enabled: false,
+        },
+    );
+
+    render_graph
+        .add_slot_edge(
+            target.color,
+            WindowSwapChainNode::OUT_TEXTURE,
+            name,
+            if msaa.samples > 1 {
+                "color_resolve_target"
+            } else {
+                "color_attachment"
+            },
+        )
+        .unwrap();
+    render_graph
+        .add_slot_edge(target.depth, WindowTextureNode::OUT_TEXTURE, name, "depth")
+        .unwrap();
+    if msaa.samples > 1 {
+        let sampled_color = target
+            .sampled_color
+            .expect("gizmo pass with msaa needs a sampled color node");
+        render_graph
+            .add_slot_edge(
+                sampled_color,
+                WindowTextureNode::OUT_TEXTURE,
+                name,
+                "color_attachment",
+            )
+            .unwrap();
+    }
+
+    render_graph.add_node_edge(target.after, name).unwrap();
+    render_graph
+        .add_node_edge(GIZMO_MATERIAL_NODE, name)
+        .unwrap();
+    render_graph.add_node_edge(SCREEN_INFO_NODE, name).unwrap();
+    if render_graph.get_node_id(UI_PASS).is_ok() {
+        render_graph.add_node_edge(name, UI_PASS).unwrap();
+    }
+}
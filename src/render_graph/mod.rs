@@ -2,27 +2,42 @@ use bevy::{
     prelude::*,
     reflect::TypeUuid,
     render::{
+        camera::Camera,
         pipeline::*,
-        render_graph::{base, RenderGraph, RenderResourcesNode},
+        render_graph::{RenderGraph, RenderResourcesNode},
         shader::{Shader, ShaderStage, ShaderStages},
         texture::TextureFormat,
     },
+    utils::HashMap,
 };
 
+mod gizmo_pass;
 mod screen_info_node;
 
-use crate::GizmoMaterial;
+use gizmo_pass::GizmoPassNode;
+pub use gizmo_pass::{add_gizmo_pass, GizmoPass, GizmoPassTarget, GIZMO_PASS};
 
-// TODO: Overlay pipeline
+use crate::{GizmoCamera, GizmoMaterial};
+
+pub const GIZMO_MATERIAL_NODE: &str = "gizmo_material";
 
 // NOTE: generated using python `import secrets; secrets.token_hex(8)`
 pub const GIZMOS_PIPELINE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x936896ad9d35720c_u64);
 
+/// Same as [`GIZMOS_PIPELINE_HANDLE`] but always drawn on top of everything else
+pub const GIZMOS_OVERLAY_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x4c1f0e7b92a3d856_u64);
+
+/// Same as [`GIZMOS_PIPELINE_HANDLE`] but writes the depth, so 2D gizmos are sorted by their z layer
+pub const GIZMOS_2D_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 0x07d2b5e1a96c43f8_u64);
+
 pub(crate) fn gizmos_pipeline_setup(
     mut pipelines: ResMut<Assets<PipelineDescriptor>>,
     mut shaders: ResMut<Assets<Shader>>,
     mut render_graph: ResMut<RenderGraph>,
+    msaa: Res<Msaa>,
 ) {
     // Pipeline setup
 
@@ -88,21 +103,65 @@ pub(crate) fn gizmos_pipeline_setup(
 
     // gizmo_pipeline.depth_stencil = None; // No depth
 
+    // Overlay ignores the depth buffer
+    let mut overlay_pipeline = gizmo_pipeline.clone();
+    if let Some(depth_stencil) = &mut overlay_pipeline.depth_stencil {
+        depth_stencil.depth_compare = CompareFunction::Always;
+    }
+
+    // Same depth settings as the sprites, also 2D cameras might be flipped
+    let mut pipeline_2d = gizmo_pipeline.clone();
+    pipeline_2d.primitive.cull_mode = CullMode::None;
+    if let Some(depth_stencil) = &mut pipeline_2d.depth_stencil {
+        depth_stencil.depth_write_enabled = true;
+        depth_stencil.depth_compare = CompareFunction::LessEqual;
+    }
+
     pipelines.set_untracked(GIZMOS_PIPELINE_HANDLE, gizmo_pipeline);
+    pipelines.set_untracked(GIZMOS_OVERLAY_PIPELINE_HANDLE, overlay_pipeline);
+    pipelines.set_untracked(GIZMOS_2D_PIPELINE_HANDLE, pipeline_2d);
+
+    // Render graph setup
 
     render_graph.add_system_node(
-        "gizmo_material",
+        GIZMO_MATERIAL_NODE,
         RenderResourcesNode::<GizmoMaterial>::new(true),
     );
-    render_graph
-        .add_node_edge("gizmo_material", base::node::MAIN_PASS)
-        .unwrap();
-
     render_graph.add_system_node(
         screen_info_node::SCREEN_INFO_NODE,
         screen_info_node::ScreenInfoNode::default(),
     );
-    render_graph
-        .add_node_edge(screen_info_node::SCREEN_INFO_NODE, base::node::MAIN_PASS)
-        .unwrap();
+
+    add_gizmo_pass(
+        &mut render_graph,
+        &msaa,
+        GIZMO_PASS,
+        GizmoPassTarget::default(),
+    );
+}
+
+/// Adds each [`GizmoCamera`] to its gizmo pass, once the pass exists
+pub(crate) fn gizmos_render_graph_system(
+    mut registered: Local<HashMap<&'static str, Vec<String>>>,
+    mut render_graph: ResMut<RenderGraph>,
+    cameras: Query<(&Camera, &GizmoCamera)>,
+) {
+    for (camera, gizmo_camera) in cameras.iter() {
+        // Passes find their cameras by name
+        let name = if let Some(name) = &camera.name {
+            name
+        } else {
+            continue;
+        };
+
+        let names = registered.entry(gizmo_camera.pass).or_default();
+        if names.contains(name) {
+            continue;
+        }
+
+        if let Ok(pass) = render_graph.get_node_mut::<GizmoPassNode>(gizmo_camera.pass) {
+            pass.add_camera(name);
+            names.push(name.clone());
+        }
+    }
 }
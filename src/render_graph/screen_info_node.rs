@@ -1,20 +1,22 @@
 // TODO: Move rendering and pipeline stuff here
 
 use bevy::{
-    app::{Events, ManualEventReader},
     core::AsBytes,
     ecs::system::BoxedSystem,
     prelude::*,
     render::{
+        camera::{ActiveCameras, Camera},
         render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
         renderer::{
             BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
-            RenderResourceBindings, RenderResourceContext,
+            RenderResourceContext,
         },
     },
-    window::{WindowCreated, WindowId, WindowResized},
+    utils::HashMap,
 };
 
+use crate::{GizmoCamera, Gizmos};
+
 pub const SCREEN_INFO_NODE: &str = "screen_info";
 pub const SCREEN_INFO_UNIFORM: &str = "ScreenInfo";
 
@@ -39,8 +41,6 @@ impl SystemNode for ScreenInfoNode {
     fn get_system(&self) -> BoxedSystem {
         let system = lights_node_system.system().config(|config| {
             config.0 = Some(ScreenInfoState {
-                screen_info_buffer: None,
-                staging_buffer: None,
                 command_queue: self.command_queue.clone(),
                 ..Default::default()
             })
@@ -51,88 +51,114 @@ impl SystemNode for ScreenInfoNode {
 
 #[derive(Default)]
 pub struct ScreenInfoState {
-    window_id: WindowId,
-    window_created_event_reader: ManualEventReader<WindowCreated>,
-    window_resized_event_reader: ManualEventReader<WindowResized>,
-    screen_info_buffer: Option<BufferId>,
-    staging_buffer: Option<BufferId>,
+    /// Screen info and staging buffers of each camera, with the size written to them
+    cameras: HashMap<Entity, (BufferId, BufferId, Vec2)>,
     command_queue: CommandQueue,
 }
 
+/// Writes the screen info of each [`GizmoCamera`] and binds it to the camera rather than to the
+/// gizmos, so every pass drawing for a camera gets the size of its own window or render target
 pub fn lights_node_system(
     mut state: Local<ScreenInfoState>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    window_created_events: Res<Events<WindowCreated>>,
-    window_resized_events: Res<Events<WindowResized>>,
     windows: Res<Windows>,
-    // TODO: this write on RenderResourceBindings will prevent this system from running in parallel
-    // with other systems that do the same
-    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+    gizmos: Res<Gizmos>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    cameras_query: Query<(Entity, &Camera, &GizmoCamera)>,
 ) {
     const BUFFER_SIZE: usize = std::mem::size_of::<[f32; 4]>();
 
-    let state = &mut state;
-    let window = windows.get(state.window_id).unwrap();
+    if !gizmos.is_enabled() {
+        return;
+    }
 
-    let staging_buffer = if let Some(staging_buffer) = state.staging_buffer {
-        staging_buffer
-    } else {
-        let buffer = render_resource_context.create_buffer(BufferInfo {
-            size: BUFFER_SIZE,
-            buffer_usage: BufferUsage::COPY_DST | BufferUsage::UNIFORM,
-            ..Default::default()
-        });
-        render_resource_bindings.set(
-            SCREEN_INFO_UNIFORM,
-            RenderResourceBinding::Buffer {
-                buffer,
-                range: 0..BUFFER_SIZE as u64,
-                dynamic_index: None,
-            },
-        );
-        state.screen_info_buffer = Some(buffer);
-
-        let staging_buffer = render_resource_context.create_buffer(BufferInfo {
-            size: BUFFER_SIZE,
-            buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
-            ..Default::default()
+    let render_resource_context = &**render_resource_context;
+    let ScreenInfoState {
+        cameras,
+        command_queue,
+    } = &mut *state;
+
+    // Buffers of removed cameras
+    cameras.retain(|entity, (screen_info_buffer, staging_buffer, _)| {
+        if cameras_query.get(*entity).is_ok() {
+            true
+        } else {
+            render_resource_context.remove_buffer(*screen_info_buffer);
+            render_resource_context.remove_buffer(*staging_buffer);
+            false
+        }
+    });
+
+    for (entity, camera, gizmo_camera) in cameras_query.iter() {
+        let size = gizmo_camera.target_size.or_else(|| {
+            windows.get(camera.window).map(|window| {
+                Vec2::new(
+                    window.physical_width() as f32,
+                    window.physical_height() as f32,
+                )
+            })
         });
+        let size = match size {
+            Some(size) if size.x > 0.0 && size.y > 0.0 => size,
+            // Nothing to draw into, like a minimized window
+            _ => continue,
+        };
+
+        let (screen_info_buffer, staging_buffer, written) =
+            cameras.entry(entity).or_insert_with(|| {
+                let buffer = render_resource_context.create_buffer(BufferInfo {
+                    size: BUFFER_SIZE,
+                    buffer_usage: BufferUsage::COPY_DST | BufferUsage::UNIFORM,
+                    ..Default::default()
+                });
+                let staging_buffer = render_resource_context.create_buffer(BufferInfo {
+                    size: BUFFER_SIZE,
+                    buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+                    ..Default::default()
+                });
+                (buffer, staging_buffer, Vec2::ZERO)
+            });
+
+        if *written != size {
+            *written = size;
+
+            let aspect = size.x / size.y;
+            let screen_info: [f32; 4] = [size.x, size.y, 1.0 / aspect, aspect];
+
+            render_resource_context.map_buffer(*staging_buffer, BufferMapMode::Write);
+            render_resource_context.write_mapped_buffer(
+                *staging_buffer,
+                0..BUFFER_SIZE as u64,
+                &mut |data, _renderer| {
+                    data[0..BUFFER_SIZE].copy_from_slice(screen_info.as_bytes());
+                },
+            );
+            render_resource_context.unmap_buffer(*staging_buffer);
+
+            command_queue.copy_buffer_to_buffer(
+                *staging_buffer,
+                0,
+                *screen_info_buffer,
+                0,
+                BUFFER_SIZE as u64,
+            );
+        }
 
-        state.staging_buffer = Some(staging_buffer);
-        staging_buffer
-    };
-
-    if state
-        .window_created_event_reader
-        .iter(&window_created_events)
-        .any(|e| e.id == window.id())
-        || state
-            .window_resized_event_reader
-            .iter(&window_resized_events)
-            .any(|e| e.id == window.id())
-    {
-        let w = window.physical_width() as f32;
-        let h = window.physical_height() as f32;
-        let aspect = w / h;
-        let screen_info: [f32; 4] = [w, h, 1.0 / aspect, aspect];
-
-        render_resource_context.map_buffer(staging_buffer, BufferMapMode::Write);
-        render_resource_context.write_mapped_buffer(
-            staging_buffer,
-            0..BUFFER_SIZE as u64,
-            &mut |data, _renderer| {
-                data[0..BUFFER_SIZE].copy_from_slice(screen_info.as_bytes());
-            },
-        );
-        render_resource_context.unmap_buffer(staging_buffer);
-
-        let screen_info_buffer = state.screen_info_buffer.unwrap();
-        state.command_queue.copy_buffer_to_buffer(
-            staging_buffer,
-            0,
-            screen_info_buffer,
-            0,
-            BUFFER_SIZE as u64,
-        );
+        // Passes bind the camera bindings after each pipeline change, like the view projection
+        let binding = RenderResourceBinding::Buffer {
+            buffer: *screen_info_buffer,
+            range: 0..BUFFER_SIZE as u64,
+            dynamic_index: None,
+        };
+        let active_camera = camera
+            .name
+            .as_deref()
+            .and_then(|name| active_cameras.get_mut(name))
+            .filter(|active_camera| active_camera.entity == Some(entity));
+        if let Some(active_camera) = active_camera {
+            if active_camera.bindings.get(SCREEN_INFO_UNIFORM) != Some(&binding) {
+                active_camera.bindings.set(SCREEN_INFO_UNIFORM, binding);
+            }
+        }
     }
 }
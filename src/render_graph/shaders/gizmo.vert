@@ -15,6 +15,10 @@ layout(location = 3) in vec3 Vertex_Normal;
 layout(location = 2) out vec3 v_Normal;
 #endif
 
+#ifdef GIZMOMATERIAL_SCREEN_OFFSET
+layout(location = 4) in vec2 Vertex_Offset;
+#endif
+
 layout(set = 0, binding = 0) uniform CameraViewProj {
     mat4 ViewProj;
 };
@@ -34,16 +38,28 @@ layout(set = 3, binding = 1) uniform GizmoMaterial_billboard_size {
 };
 #endif
 
+#ifdef GIZMOMATERIAL_DEFORM
+layout(set = 3, binding = 4) uniform GizmoMaterial_deform_matrix {
+    mat4 Deform;
+};
+#endif
+
 void main() {
     v_Color = Vertex_Color;
 
+#ifdef GIZMOMATERIAL_DEFORM
+    mat4 model = Deform * Model;
+#else
+    mat4 model = Model;
+#endif
+
 #ifdef GIZMOMATERIAL_TEXTURE
     v_Uv = Vertex_Uv;
 #endif
 
 
 #ifdef GIZMOMATERIAL_BILLBOARD
-    gl_Position = ViewProj * vec4(Model[3].xyz, 1.0);
+    gl_Position = ViewProj * vec4(model[3].xyz, 1.0);
     gl_Position /= gl_Position.w;
     gl_Position.xy += Vertex_Position.xy * vec2(ScreenAspectRatio.x, 1.0) * BillboadSize * 0.2;
 
@@ -52,10 +68,20 @@ void main() {
     v_Normal = Vertex_Normal;
 #endif
 #else
-    gl_Position = ViewProj * vec4((Model * vec4(Vertex_Position, 1.0)).xyz, 1.0);
+#ifdef GIZMOMATERIAL_SCREEN_SPACE
+    // Pixels to clip space, in front of everything
+    gl_Position = vec4((model * vec4(Vertex_Position, 1.0)).xy * 2.0 / ScreenSize - 1.0, 0.0, 1.0);
+#else
+    gl_Position = ViewProj * vec4((model * vec4(Vertex_Position, 1.0)).xyz, 1.0);
+#endif
+
+#ifdef GIZMOMATERIAL_SCREEN_OFFSET
+    // Pixels to clip space, scaled by w to be constant after the perspective divide
+    gl_Position.xy += Vertex_Offset * 2.0 / ScreenSize * gl_Position.w;
+#endif
 
 #ifdef GIZMOMATERIAL_LIT
-    v_Normal = mat3(Model) * Vertex_Normal;
+    v_Normal = mat3(model) * Vertex_Normal;
 #endif
 #endif
 }
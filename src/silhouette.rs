@@ -0,0 +1,162 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    prelude::*,
+    render::camera::{Camera, RenderLayers},
+};
+
+use crate::{
+    camera::{camera_layers, is_orthographic, GizmoCameraLayer},
+    GizmoMaterial, Gizmos,
+};
+
+const SILHOUETTE_SEGMENTS: usize = 32;
+
+/// Draws the view dependent outline of every sphere and capsule wireframe,
+/// making them easier to read from any angle; each [`crate::GizmoCamera`] gets its own outlines
+pub struct GizmoSilhouettes {
+    pub enabled: bool,
+    /// Draw mask used when drawing the outlines, see [`Gizmos::draw`]
+    pub mask: u32,
+}
+
+impl Default for GizmoSilhouettes {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mask: u32::MAX,
+        }
+    }
+}
+
+/// Marks a wireframe mesh that should have his silhouette drawn
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Silhouette {
+    /// Unit sphere mesh
+    Sphere,
+    /// Unit cylinder mesh used as a capsule body
+    Capsule,
+}
+
+pub(crate) fn gizmos_silhouette_system(
+    silhouettes: Res<GizmoSilhouettes>,
+    gizmos: Res<Gizmos>,
+    cameras: Query<(
+        &Camera,
+        &GlobalTransform,
+        Option<&RenderLayers>,
+        &GizmoCameraLayer,
+    )>,
+    query: Query<(
+        &Silhouette,
+        &GlobalTransform,
+        &GizmoMaterial,
+        &Visible,
+        &RenderLayers,
+    )>,
+) {
+    if !silhouettes.enabled || !gizmos.is_enabled() {
+        return;
+    }
+
+    gizmos.draw(silhouettes.mask, |mut context| {
+        // Outlines are only right for the camera they were made for,
+        // so each camera draws its own on its private layer
+        for (camera, camera_transform, layers, private_layer) in cameras.iter() {
+            let seen = camera_layers(layers);
+            let orthographic = is_orthographic(camera);
+            let eye = camera_transform.translation;
+            let forward = camera_transform.rotation * -Vec3::Z;
+
+            context.with_layers(RenderLayers::layer(private_layer.0));
+
+            for (silhouette, global_transform, material, visible, layers) in query.iter() {
+                if !visible.is_visible || !seen.intersects(layers) {
+                    continue;
+                }
+
+                context.with_wireframe(material.color);
+
+                let radius = global_transform.scale.x.max(global_transform.scale.z);
+                let center = global_transform.translation;
+
+                match silhouette {
+                    Silhouette::Sphere => {
+                        if let Some((c, r, n)) =
+                            sphere_outline(center, radius, eye, forward, orthographic)
+                        {
+                            context.line_list(circle(c, r, n), 0.0);
+                        }
+                    }
+                    Silhouette::Capsule => {
+                        let axis = global_transform.rotation * Vec3::Y;
+                        let top = center + axis * (global_transform.scale.y * 0.5);
+                        let bottom = center - axis * (global_transform.scale.y * 0.5);
+
+                        for p in [top, bottom].iter().copied() {
+                            if let Some((c, r, n)) =
+                                sphere_outline(p, radius, eye, forward, orthographic)
+                            {
+                                context.line_list(circle(c, r, n), 0.0);
+                            }
+                        }
+
+                        // Side lines
+                        let view = if orthographic { forward } else { center - eye };
+                        let side = axis.cross(view);
+                        if side.length_squared() > f32::EPSILON {
+                            let side = side.normalize() * radius;
+                            context.line_list([top + side, bottom + side], 0.0);
+                            context.line_list([top - side, bottom - side], 0.0);
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Circle (center, radius and normal) of the sphere outline as seen from the camera,
+/// returns `None` when the camera is inside the sphere
+fn sphere_outline(
+    center: Vec3,
+    radius: f32,
+    eye: Vec3,
+    forward: Vec3,
+    orthographic: bool,
+) -> Option<(Vec3, f32, Vec3)> {
+    if orthographic {
+        return Some((center, radius, forward));
+    }
+
+    let to_eye = eye - center;
+    let d2 = to_eye.length_squared();
+    let r2 = radius * radius;
+    if d2 <= r2 {
+        return None;
+    }
+
+    // The outline is slightly smaller and closer to the camera than the sphere great circle
+    let k = r2 / d2;
+    Some((
+        center + to_eye * k,
+        radius * (1.0 - k).sqrt(),
+        to_eye / d2.sqrt(),
+    ))
+}
+
+fn circle(center: Vec3, radius: f32, normal: Vec3) -> impl Iterator<Item = Vec3> {
+    let other = if normal.x.abs() < 0.9 {
+        Vec3::X
+    } else {
+        Vec3::Z
+    };
+    let u = normal.cross(other).normalize() * radius;
+    let v = normal.cross(u);
+
+    (0..=SILHOUETTE_SEGMENTS).map(move |i| {
+        let t = (i as f32) * (2.0 / SILHOUETTE_SEGMENTS as f32);
+        let (s, c) = f32::sin_cos(t * PI);
+        center + u * c + v * s
+    })
+}